@@ -0,0 +1,95 @@
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::PathBuf;
+use sysinfo::{Pid, ProcessesToUpdate, System};
+use tauri::{AppHandle, Manager};
+
+use super::sampler::now_secs;
+
+const AUDIT_FILE: &str = "process_audit.log";
+const MAX_AUDIT_BYTES: u64 = 1_048_576;
+const DEFAULT_AUDIT_LIMIT: usize = 200;
+
+// One line of JSON per action taken on a process, including refusals.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct AuditEntry {
+    pub timestamp: u64,
+    pub action: String, // "kill", "suspend", "resume", "priority", "affinity", "cap", "uncap"
+    pub pid: u32,
+    pub name: String,
+    pub success: bool,
+    pub message: String,
+}
+
+fn audit_path(app: &AppHandle) -> Option<PathBuf> {
+    app.path()
+        .app_data_dir()
+        .ok()
+        .map(|dir| dir.join(AUDIT_FILE))
+}
+
+// Looked up before acting, since a killed process can't be asked afterwards.
+pub fn process_name(pid: u32) -> String {
+    let pid = Pid::from_u32(pid);
+    let mut sys = System::new();
+    sys.refresh_processes(ProcessesToUpdate::Some(&[pid]), true);
+    sys.process(pid)
+        .map(|p| p.name().to_string_lossy().to_string())
+        .unwrap_or_default()
+}
+
+pub fn record(app: &AppHandle, action: &str, pid: u32, name: &str, success: bool, message: &str) {
+    let Some(path) = audit_path(app) else {
+        return;
+    };
+    if let Some(dir) = path.parent() {
+        let _ = fs::create_dir_all(dir);
+    }
+    // Keep one previous file rather than growing without bound
+    if fs::metadata(&path).is_ok_and(|m| m.len() > MAX_AUDIT_BYTES) {
+        let _ = fs::rename(&path, path.with_extension("log.1"));
+    }
+
+    let entry = AuditEntry {
+        timestamp: now_secs(),
+        action: action.into(),
+        pid,
+        name: name.into(),
+        success,
+        message: message.into(),
+    };
+    if let (Ok(json), Ok(mut file)) = (
+        serde_json::to_string(&entry),
+        OpenOptions::new().create(true).append(true).open(&path),
+    ) {
+        let _ = writeln!(file, "{json}");
+    }
+}
+
+pub fn record_result<T, E: fmt::Display>(
+    app: &AppHandle,
+    action: &str,
+    pid: u32,
+    name: &str,
+    result: &Result<T, E>,
+    done: &str,
+) {
+    match result {
+        Ok(_) => record(app, action, pid, name, true, done),
+        Err(e) => record(app, action, pid, name, false, &e.to_string()),
+    }
+}
+
+#[tauri::command]
+pub fn get_audit_log(app: AppHandle, limit: Option<usize>) -> Vec<AuditEntry> {
+    let text = audit_path(&app)
+        .and_then(|path| fs::read_to_string(path).ok())
+        .unwrap_or_default();
+    text.lines()
+        .rev()
+        .filter_map(|line| serde_json::from_str(line).ok())
+        .take(limit.unwrap_or(DEFAULT_AUDIT_LIMIT))
+        .collect()
+}
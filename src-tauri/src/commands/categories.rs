@@ -0,0 +1,405 @@
+use regex::{Regex, RegexBuilder};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::PathBuf;
+use std::sync::Mutex;
+use sysinfo::{Process, System};
+use tauri::{AppHandle, Manager, State};
+
+const RULES_FILE: &str = "process_categories.json";
+const FALLBACK_CATEGORY: &str = "Other";
+
+// Evaluated top to bottom; the first rule whose matchers all hold wins.
+const DEFAULT_RULES: &[(&str, &str, &[&str])] = &[
+    (
+        "Browser",
+        "globe",
+        &["*chrome*", "*firefox*", "*edge*", "*opera*"],
+    ),
+    (
+        "Developer",
+        "code",
+        &[
+            "*code*",
+            "*cursor*",
+            "*windsurf*",
+            "*node*",
+            "*python*",
+            "*rust*",
+            "*java*",
+            "*dotnet*",
+            "*postgres*",
+        ],
+    ),
+    (
+        "Communication",
+        "message-circle",
+        &["*discord*", "*slack*", "*teams*", "*zoom*"],
+    ),
+    (
+        "Gaming",
+        "gamepad-2",
+        &["*steam*", "*epic*", "*battle.net*", "*game*"],
+    ),
+    (
+        "System",
+        "cpu",
+        &[
+            "*svchost*",
+            "*explorer*",
+            "*dwm*",
+            "*csrss*",
+            "*system*",
+            "*services*",
+            "*lsass*",
+            "*winlogon*",
+        ],
+    ),
+];
+
+// Glob lists match if any pattern does; `*` and `?` wildcards, case-insensitive.
+// An empty list is not checked.
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[serde(default)]
+pub struct CategoryRule {
+    pub category: String,
+    pub icon: Option<String>,
+    pub exe_name: Vec<String>,
+    pub exe_path: Vec<String>,
+    pub cmdline: Option<String>, // regex
+    pub parent: Vec<String>,     // parent's exe name
+}
+
+// What a rule gets to look at, so evaluation needs no live System.
+#[derive(Debug, Default)]
+pub struct ProcessFacts {
+    pub name: String,
+    pub exe_path: Option<String>,
+    pub cmdline: String,
+    pub parent_name: Option<String>,
+}
+
+impl ProcessFacts {
+    pub fn of(sys: &System, process: &Process) -> Self {
+        ProcessFacts {
+            name: process.name().to_string_lossy().to_string(),
+            exe_path: process.exe().map(|p| p.to_string_lossy().to_string()),
+            cmdline: process
+                .cmd()
+                .iter()
+                .map(|a| a.to_string_lossy())
+                .collect::<Vec<_>>()
+                .join(" "),
+            parent_name: process
+                .parent()
+                .and_then(|pid| sys.process(pid))
+                .map(|p| p.name().to_string_lossy().to_string()),
+        }
+    }
+}
+
+pub struct Category {
+    pub name: String,
+    pub icon: Option<String>,
+}
+
+struct CompiledRule {
+    rule: CategoryRule,
+    cmdline: Option<Regex>,
+}
+
+pub struct Categorizer {
+    rules: Vec<CompiledRule>,
+}
+
+pub type CategoryState = Mutex<Categorizer>;
+
+pub fn default_rules() -> Vec<CategoryRule> {
+    DEFAULT_RULES
+        .iter()
+        .map(|(category, icon, names)| CategoryRule {
+            category: category.to_string(),
+            icon: Some(icon.to_string()),
+            exe_name: names.iter().map(|n| n.to_string()).collect(),
+            ..Default::default()
+        })
+        .collect()
+}
+
+fn glob_match(pattern: &str, text: &str) -> bool {
+    let pattern: Vec<char> = pattern.to_lowercase().chars().collect();
+    let text: Vec<char> = text.to_lowercase().chars().collect();
+    let (mut p, mut t) = (0, 0);
+    let mut backtrack: Option<(usize, usize)> = None;
+
+    while t < text.len() {
+        match pattern.get(p) {
+            Some('*') => {
+                backtrack = Some((p, t));
+                p += 1;
+            }
+            Some(&c) if c == '?' || c == text[t] => {
+                p += 1;
+                t += 1;
+            }
+            _ => match backtrack {
+                // let the last `*` swallow one more character
+                Some((star, consumed)) => {
+                    p = star + 1;
+                    t = consumed + 1;
+                    backtrack = Some((star, consumed + 1));
+                }
+                None => return false,
+            },
+        }
+    }
+    pattern[p..].iter().all(|&c| c == '*')
+}
+
+fn any_glob(patterns: &[String], text: Option<&str>) -> bool {
+    if patterns.is_empty() {
+        return true;
+    }
+    let Some(text) = text else {
+        return false;
+    };
+    patterns.iter().any(|p| glob_match(p, text))
+}
+
+// "chrome.exe" matches both `chrome` and `chrome.exe` style patterns.
+fn name_matches(patterns: &[String], name: Option<&str>) -> bool {
+    let stem = name.map(|n| {
+        n.len()
+            .checked_sub(4)
+            .filter(|&i| n.is_char_boundary(i) && n[i..].eq_ignore_ascii_case(".exe"))
+            .map_or(n, |i| &n[..i])
+    });
+    any_glob(patterns, name) || any_glob(patterns, stem)
+}
+
+impl CompiledRule {
+    fn matches(&self, facts: &ProcessFacts) -> bool {
+        name_matches(&self.rule.exe_name, Some(&facts.name))
+            && any_glob(&self.rule.exe_path, facts.exe_path.as_deref())
+            && name_matches(&self.rule.parent, facts.parent_name.as_deref())
+            && self
+                .cmdline
+                .as_ref()
+                .is_none_or(|re| re.is_match(&facts.cmdline))
+    }
+}
+
+impl Categorizer {
+    pub fn new(rules: Vec<CategoryRule>) -> Result<Self, String> {
+        let rules = rules
+            .into_iter()
+            .map(|rule| {
+                if rule.category.trim().is_empty() {
+                    return Err("Every rule needs a category".to_string());
+                }
+                let cmdline = match &rule.cmdline {
+                    Some(pattern) => Some(
+                        RegexBuilder::new(pattern)
+                            .case_insensitive(true)
+                            .build()
+                            .map_err(|e| {
+                                format!("{}: invalid command-line pattern: {e}", rule.category)
+                            })?,
+                    ),
+                    None => None,
+                };
+                Ok(CompiledRule { rule, cmdline })
+            })
+            .collect::<Result<_, String>>()?;
+        Ok(Categorizer { rules })
+    }
+
+    pub fn rules(&self) -> Vec<CategoryRule> {
+        self.rules.iter().map(|r| r.rule.clone()).collect()
+    }
+
+    pub fn categorize(&self, facts: &ProcessFacts) -> Category {
+        match self.rules.iter().find(|r| r.matches(facts)) {
+            Some(r) => Category {
+                name: r.rule.category.clone(),
+                icon: r.rule.icon.clone(),
+            },
+            None => Category {
+                name: FALLBACK_CATEGORY.into(),
+                icon: None,
+            },
+        }
+    }
+}
+
+impl Default for Categorizer {
+    fn default() -> Self {
+        Categorizer::new(default_rules()).expect("default category rules are valid")
+    }
+}
+
+fn rules_path(app: &AppHandle) -> Option<PathBuf> {
+    app.path()
+        .app_config_dir()
+        .ok()
+        .map(|dir| dir.join(RULES_FILE))
+}
+
+pub fn load_categorizer(app: &AppHandle) -> Categorizer {
+    rules_path(app)
+        .and_then(|path| fs::read_to_string(path).ok())
+        .and_then(|text| serde_json::from_str(&text).ok())
+        .and_then(|rules| Categorizer::new(rules).ok())
+        .unwrap_or_default()
+}
+
+#[tauri::command]
+pub fn get_category_rules(state: State<'_, CategoryState>) -> Vec<CategoryRule> {
+    state.lock().unwrap().rules()
+}
+
+#[tauri::command]
+pub fn set_category_rules(
+    app: AppHandle,
+    state: State<'_, CategoryState>,
+    rules: Option<Vec<CategoryRule>>,
+) -> Result<Vec<CategoryRule>, String> {
+    let categorizer = Categorizer::new(rules.unwrap_or_else(default_rules))?;
+    let path = rules_path(&app).ok_or("App config directory not available")?;
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir).map_err(|e| e.to_string())?;
+    }
+    let json = serde_json::to_string_pretty(&categorizer.rules()).map_err(|e| e.to_string())?;
+    fs::write(&path, json).map_err(|e| e.to_string())?;
+
+    let rules = categorizer.rules();
+    *state.lock().unwrap() = categorizer;
+    Ok(rules)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // The hard-coded matcher the default rules replaced.
+    fn baseline(name: &str) -> &'static str {
+        let lower = name.to_lowercase();
+        let has = |needles: &[&str]| needles.iter().any(|n| lower.contains(n));
+        if has(&["chrome", "firefox", "edge", "opera"]) {
+            "Browser"
+        } else if has(&[
+            "code", "cursor", "windsurf", "node", "python", "rust", "java", "dotnet", "postgres",
+        ]) {
+            "Developer"
+        } else if has(&["discord", "slack", "teams", "zoom"]) {
+            "Communication"
+        } else if has(&["steam", "epic", "battle.net", "game"]) {
+            "Gaming"
+        } else if has(&[
+            "svchost", "explorer", "dwm", "csrss", "system", "services", "lsass", "winlogon",
+        ]) {
+            "System"
+        } else {
+            "Other"
+        }
+    }
+
+    #[test]
+    fn glob_wildcards() {
+        let cases = [
+            ("*", "", true),
+            ("*", "anything", true),
+            ("", "", true),
+            ("", "a", false),
+            ("?", "", false),
+            ("?", "a", true),
+            ("?", "ab", false),
+            ("a?c", "abc", true),
+            ("a?c", "ac", false),
+            ("chrome*", "chrome.exe", true),
+            ("chrome*", "google-chrome", false),
+            ("*chrome", "google-chrome", true),
+            ("*chrome", "chrome.exe", false),
+            ("*chrome*", "chrome", true),
+            ("*a*b", "xaxxb", true),
+            ("*a*b", "xaxxbc", false),
+            ("**", "x", true),
+            ("*.exe", "Code.EXE", true),
+            ("FireFox", "firefox", true),
+            ("firefox", "firefox-bin", false),
+        ];
+        for (pattern, text, expected) in cases {
+            assert_eq!(
+                glob_match(pattern, text),
+                expected,
+                "{pattern:?} vs {text:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn empty_pattern_list_always_matches() {
+        assert!(any_glob(&[], None));
+        assert!(any_glob(&[], Some("x")));
+        assert!(!any_glob(&["*".into()], None));
+    }
+
+    #[test]
+    fn exe_suffix_is_optional() {
+        let patterns = vec!["chrome".to_string()];
+        assert!(name_matches(&patterns, Some("chrome.exe")));
+        assert!(name_matches(&patterns, Some("CHROME.EXE")));
+        assert!(!name_matches(&patterns, Some("chrome.bin")));
+    }
+
+    #[test]
+    fn default_rules_match_baseline() {
+        let categorizer = Categorizer::default();
+        let names = [
+            "chrome",
+            "chrome.exe",
+            "firefox-bin",
+            "msedge.exe",
+            "opera",
+            "code",
+            "Code.exe",
+            "cursor",
+            "node",
+            "python3.12",
+            "rust-analyzer",
+            "java",
+            "dotnet",
+            "postgres",
+            "Discord",
+            "slack",
+            "Teams.exe",
+            "zoom",
+            "steam",
+            "EpicGamesLauncher.exe",
+            "Battle.net.exe",
+            "gamescope",
+            "svchost.exe",
+            "explorer.exe",
+            "dwm.exe",
+            "csrss.exe",
+            "systemd",
+            "services.exe",
+            "lsass.exe",
+            "winlogon.exe",
+            "bash",
+            "Xorg",
+            "",
+        ];
+        for name in names {
+            let facts = ProcessFacts {
+                name: name.into(),
+                ..Default::default()
+            };
+            assert_eq!(
+                categorizer.categorize(&facts).name,
+                baseline(name),
+                "{name:?}"
+            );
+        }
+    }
+}
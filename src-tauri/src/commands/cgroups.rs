@@ -0,0 +1,479 @@
+use serde::{Deserialize, Serialize};
+use sysinfo::Pid;
+use tauri::{AppHandle, State};
+
+use super::audit;
+use super::memory::{CommandError, SharedSystem};
+use super::protection::PolicyState;
+
+#[cfg(target_os = "linux")]
+use std::fs;
+#[cfg(target_os = "linux")]
+use std::path::{Path, PathBuf};
+#[cfg(target_os = "linux")]
+use std::process::{Command, Stdio};
+#[cfg(target_os = "linux")]
+use std::thread;
+#[cfg(target_os = "linux")]
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+#[cfg(target_os = "linux")]
+use super::cleanup::{is_elevated, run_tool};
+
+#[cfg(target_os = "linux")]
+const CGROUP_ROOT: &str = "/sys/fs/cgroup";
+#[cfg(target_os = "linux")]
+const UNIT_PREFIX: &str = "system-cleaner-cap-";
+#[cfg(target_os = "linux")]
+const MAX_SEARCH_DEPTH: u32 = 8;
+#[cfg(target_os = "linux")]
+const ATTACH_TIMEOUT: Duration = Duration::from_secs(2);
+#[cfg(target_os = "linux")]
+const LAUNCH_CHECK: Duration = Duration::from_millis(500);
+const MIN_LIMIT_BYTES: u64 = 16 * 1_048_576;
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CapTarget {
+    Pid(u32),
+    Command(Vec<String>), // program followed by its arguments
+}
+
+#[derive(Debug, Deserialize, Clone, Copy)]
+pub struct MemoryLimits {
+    pub max_bytes: Option<u64>, // hard limit; past it the kernel OOM-kills inside the scope
+    pub high_bytes: Option<u64>, // soft limit; above it the scope is throttled and reclaimed
+}
+
+#[derive(Debug, Serialize)]
+pub struct MemoryCap {
+    pub unit: String,
+    pub user: bool, // run by the user's systemd instance rather than the system one
+    pub pids: Vec<u32>,
+    pub names: Vec<String>,
+    pub memory_bytes: u64,
+    pub max_bytes: Option<u64>, // None when unlimited
+    pub high_bytes: Option<u64>,
+    pub high_events: u64, // times usage went over the high limit
+    pub oom_kills: u64,
+}
+
+#[cfg(target_os = "linux")]
+fn unit_name() -> String {
+    let nanos = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_nanos())
+        .unwrap_or(0);
+    format!("{UNIT_PREFIX}{nanos}.scope")
+}
+
+fn check_limits(limits: &MemoryLimits) -> Result<(), CommandError> {
+    let message = match (limits.max_bytes, limits.high_bytes) {
+        (None, None) => "Set a maximum, a high limit or both",
+        (Some(max), Some(high)) if high > max => "The high limit must be below the maximum",
+        (max, high) if max.into_iter().chain(high).any(|b| b < MIN_LIMIT_BYTES) => {
+            "Limits under 16 MB would stop most programs outright"
+        }
+        _ => return Ok(()),
+    };
+    Err(CommandError::Invalid {
+        message: message.into(),
+    })
+}
+
+// systemd owns the cgroup tree, so caps are transient scopes it creates for
+// us. Unprivileged, that is the user's own systemd instance, which can only
+// set memory limits if the memory controller is delegated to it.
+#[cfg(target_os = "linux")]
+fn check_support(user: bool) -> Result<(), CommandError> {
+    let unsupported = |message: &str| CommandError::Unsupported {
+        message: message.into(),
+    };
+    if !Path::new(CGROUP_ROOT).join("cgroup.controllers").exists() {
+        return Err(unsupported(
+            "Memory caps need the unified cgroup v2 hierarchy, but this system uses cgroup v1",
+        ));
+    }
+    if !Path::new("/run/systemd/system").exists() {
+        return Err(unsupported("Memory caps need systemd to manage cgroups"));
+    }
+
+    let root = if user {
+        let uid = unsafe { libc::geteuid() };
+        PathBuf::from(format!(
+            "{CGROUP_ROOT}/user.slice/user-{uid}.slice/user@{uid}.service"
+        ))
+    } else {
+        PathBuf::from(CGROUP_ROOT)
+    };
+    let controllers = fs::read_to_string(root.join("cgroup.controllers"))
+        .map_err(|_| unsupported("The systemd user manager isn't running"))?;
+    if !controllers.split_whitespace().any(|c| c == "memory") {
+        return Err(unsupported(
+            "The memory controller isn't delegated to your user. Add Delegate=memory to user@.service, or run the app as administrator",
+        ));
+    }
+    Ok(())
+}
+
+#[cfg(target_os = "linux")]
+fn properties(limits: &MemoryLimits) -> Vec<(&'static str, u64)> {
+    [
+        ("MemoryMax", limits.max_bytes),
+        ("MemoryHigh", limits.high_bytes),
+    ]
+    .into_iter()
+    .filter_map(|(name, value)| Some((name, value?)))
+    .collect()
+}
+
+// systemd-run creates the scope, then execs the command in it, so the child
+// is the capped process itself.
+#[cfg(target_os = "linux")]
+fn launch(
+    unit: &str,
+    command: &[String],
+    limits: &MemoryLimits,
+    user: bool,
+) -> Result<u32, CommandError> {
+    let (program, args) = command.split_first().ok_or_else(|| CommandError::Invalid {
+        message: "Enter a command to run".into(),
+    })?;
+    let mut cmd = Command::new("systemd-run");
+    if user {
+        cmd.arg("--user");
+    }
+    cmd.args(["--scope", "--quiet", "--collect"])
+        .arg(format!("--unit={unit}"));
+    for (name, value) in properties(limits) {
+        cmd.arg("-p").arg(format!("{name}={value}"));
+    }
+    cmd.arg("--").arg(program).args(args).stdin(Stdio::null());
+
+    let mut child = cmd.spawn().map_err(|e| CommandError::Failed {
+        message: format!("Failed to run systemd-run: {e}"),
+    })?;
+    // An immediate failure means the scope or the command couldn't start
+    let started = Instant::now();
+    while started.elapsed() < LAUNCH_CHECK {
+        match child.try_wait() {
+            Ok(Some(status)) if !status.success() => {
+                return Err(CommandError::Failed {
+                    message: format!("{program} could not be started in a capped scope ({status})"),
+                });
+            }
+            Ok(Some(_)) => break,
+            _ => thread::sleep(Duration::from_millis(50)),
+        }
+    }
+    let pid = child.id();
+    // Reaped whenever it exits, so it doesn't linger as a zombie
+    thread::spawn(move || {
+        let _ = child.wait();
+    });
+    Ok(pid)
+}
+
+#[cfg(target_os = "linux")]
+fn in_unit(pid: u32, unit: &str) -> bool {
+    fs::read_to_string(format!("/proc/{pid}/cgroup"))
+        .is_ok_and(|text| text.lines().any(|line| line.ends_with(&format!("/{unit}"))))
+}
+
+// "0::/user.slice/user-1000.slice/user@1000.service/app.slice/system-cleaner-cap-<nanos>.scope"
+#[cfg(target_os = "linux")]
+fn is_cap_cgroup(text: &str) -> bool {
+    text.lines().any(|line| {
+        line.rsplit('/')
+            .next()
+            .is_some_and(|unit| unit.starts_with(UNIT_PREFIX) && unit.ends_with(".scope"))
+    })
+}
+
+// Processes launched or moved into a cap, and their children. systemd-run
+// execs the command as the app's own child, but it's the user's program.
+#[cfg(target_os = "linux")]
+pub fn in_cap_scope(pid: u32) -> bool {
+    fs::read_to_string(format!("/proc/{pid}/cgroup")).is_ok_and(|text| is_cap_cgroup(&text))
+}
+
+#[cfg(not(target_os = "linux"))]
+pub fn in_cap_scope(_pid: u32) -> bool {
+    false
+}
+
+// systemd-run can only start new processes; an existing one is moved into a
+// new scope with StartTransientUnit, which takes its PID.
+#[cfg(target_os = "linux")]
+fn attach(unit: &str, pid: u32, limits: &MemoryLimits, user: bool) -> Result<(), CommandError> {
+    let properties = properties(limits);
+    let mut args: Vec<String> = Vec::new();
+    if user {
+        args.push("--user".into());
+    }
+    args.extend(
+        [
+            "call",
+            "org.freedesktop.systemd1",
+            "/org/freedesktop/systemd1",
+            "org.freedesktop.systemd1.Manager",
+            "StartTransientUnit",
+            "ssa(sv)a(sa(sv))",
+            unit,
+            "fail",
+        ]
+        .map(String::from),
+    );
+    args.push((properties.len() + 2).to_string());
+    args.extend(["PIDs", "au", "1"].map(String::from));
+    args.push(pid.to_string());
+    args.extend(["CollectMode", "s", "inactive-or-failed"].map(String::from));
+    for (name, value) in properties {
+        args.extend([name.to_string(), "t".into(), value.to_string()]);
+    }
+    args.push("0".into());
+
+    let args: Vec<&str> = args.iter().map(String::as_str).collect();
+    let (ok, output) = run_tool("busctl", &args);
+    if !ok {
+        return Err(CommandError::Failed {
+            message: format!("systemd refused to create the scope: {output}"),
+        });
+    }
+
+    // The job runs after the call returns
+    let started = Instant::now();
+    while started.elapsed() < ATTACH_TIMEOUT {
+        if in_unit(pid, unit) {
+            return Ok(());
+        }
+        thread::sleep(Duration::from_millis(100));
+    }
+    Err(CommandError::Failed {
+        message:
+            "systemd couldn't move the process. It may belong to another login session or user"
+                .into(),
+    })
+}
+
+#[cfg(target_os = "linux")]
+fn find_scopes(dir: &Path, depth: u32, out: &mut Vec<PathBuf>) {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        if !entry.file_type().is_ok_and(|t| t.is_dir()) {
+            continue;
+        }
+        let name = entry.file_name().to_string_lossy().to_string();
+        if name.starts_with(UNIT_PREFIX) && name.ends_with(".scope") {
+            out.push(entry.path());
+        } else if depth < MAX_SEARCH_DEPTH {
+            find_scopes(&entry.path(), depth + 1, out);
+        }
+    }
+}
+
+// memory.max and memory.high read "max" when unlimited
+#[cfg(target_os = "linux")]
+fn read_limit(path: &Path) -> Option<u64> {
+    fs::read_to_string(path).ok()?.trim().parse().ok()
+}
+
+#[cfg(target_os = "linux")]
+fn read_event(text: &str, key: &str) -> u64 {
+    text.lines()
+        .find_map(|line| line.strip_prefix(key)?.strip_prefix(' '))
+        .and_then(|value| value.trim().parse().ok())
+        .unwrap_or(0)
+}
+
+#[cfg(target_os = "linux")]
+fn read_caps(sys: &sysinfo::System) -> Vec<MemoryCap> {
+    let mut scopes = Vec::new();
+    find_scopes(Path::new(CGROUP_ROOT), 0, &mut scopes);
+    scopes
+        .iter()
+        .filter_map(|path| {
+            let max_bytes = read_limit(&path.join("memory.max"));
+            let high_bytes = read_limit(&path.join("memory.high"));
+            // Lifted caps stay around until their processes exit
+            if max_bytes.is_none() && high_bytes.is_none() {
+                return None;
+            }
+            let pids: Vec<u32> = fs::read_to_string(path.join("cgroup.procs"))
+                .unwrap_or_default()
+                .lines()
+                .filter_map(|line| line.trim().parse().ok())
+                .collect();
+            let events = fs::read_to_string(path.join("memory.events")).unwrap_or_default();
+            Some(MemoryCap {
+                unit: path.file_name()?.to_string_lossy().to_string(),
+                user: path.to_string_lossy().contains("/user@"),
+                names: pids
+                    .iter()
+                    .filter_map(|&pid| sys.process(Pid::from_u32(pid)))
+                    .map(|p| p.name().to_string_lossy().to_string())
+                    .collect(),
+                pids,
+                memory_bytes: read_limit(&path.join("memory.current")).unwrap_or(0),
+                max_bytes,
+                high_bytes,
+                high_events: read_event(&events, "high"),
+                oom_kills: read_event(&events, "oom_kill"),
+            })
+        })
+        .collect()
+}
+
+#[cfg(not(target_os = "linux"))]
+fn read_caps(_sys: &sysinfo::System) -> Vec<MemoryCap> {
+    Vec::new()
+}
+
+#[cfg(target_os = "linux")]
+fn cap(
+    target: &CapTarget,
+    limits: &MemoryLimits,
+    policy: &State<'_, PolicyState>,
+    system: &SharedSystem,
+) -> Result<u32, CommandError> {
+    check_limits(limits)?;
+    // Root manages the system's scopes; anyone else only their own
+    let user = !is_elevated();
+    check_support(user)?;
+
+    let unit = unit_name();
+    match target {
+        CapTarget::Pid(pid) => {
+            let policy = policy.lock().unwrap().clone();
+            {
+                let sys = system.refreshed();
+                let process = sys
+                    .process(Pid::from_u32(*pid))
+                    .ok_or_else(CommandError::not_found)?;
+                policy.check(&sys, process)?;
+            }
+            attach(&unit, *pid, limits, user)?;
+            Ok(*pid)
+        }
+        CapTarget::Command(command) => launch(&unit, command, limits, user),
+    }
+}
+
+#[cfg(not(target_os = "linux"))]
+fn cap(
+    _target: &CapTarget,
+    limits: &MemoryLimits,
+    _policy: &State<'_, PolicyState>,
+    _system: &SharedSystem,
+) -> Result<u32, CommandError> {
+    check_limits(limits)?;
+    Err(CommandError::Unsupported {
+        message: "Memory caps need Linux with cgroup v2".into(),
+    })
+}
+
+// Returns the PID of the capped process.
+#[tauri::command]
+pub fn set_memory_cap(
+    app: AppHandle,
+    target: CapTarget,
+    limits: MemoryLimits,
+    policy: State<'_, PolicyState>,
+    system: State<'_, SharedSystem>,
+) -> Result<u32, CommandError> {
+    let (pid, name) = match &target {
+        CapTarget::Pid(pid) => (*pid, audit::process_name(*pid)),
+        CapTarget::Command(command) => (0, command.first().cloned().unwrap_or_default()),
+    };
+    let result = cap(&target, &limits, &policy, &system);
+    let done = format!(
+        "Capped at max {}, high {}",
+        limits
+            .max_bytes
+            .map_or("none".into(), |b| format!("{} MB", b / 1_048_576)),
+        limits
+            .high_bytes
+            .map_or("none".into(), |b| format!("{} MB", b / 1_048_576)),
+    );
+    let pid = *result.as_ref().unwrap_or(&pid);
+    audit::record_result(&app, "cap", pid, &name, &result, &done);
+    result
+}
+
+#[tauri::command]
+pub fn get_memory_caps(system: State<'_, SharedSystem>) -> Vec<MemoryCap> {
+    read_caps(&system.refreshed())
+}
+
+// Lifts the limits rather than stopping the scope, which would kill it.
+#[cfg(target_os = "linux")]
+fn uncap(cap: &MemoryCap) -> Result<(), CommandError> {
+    let mut args = vec!["set-property", "--runtime"];
+    if cap.user {
+        args.insert(0, "--user");
+    }
+    args.extend([
+        cap.unit.as_str(),
+        "MemoryMax=infinity",
+        "MemoryHigh=infinity",
+    ]);
+    let (ok, output) = run_tool("systemctl", &args);
+    if ok {
+        Ok(())
+    } else {
+        Err(CommandError::Failed {
+            message: format!("Failed to lift the cap: {output}"),
+        })
+    }
+}
+
+#[cfg(not(target_os = "linux"))]
+fn uncap(_cap: &MemoryCap) -> Result<(), CommandError> {
+    Err(CommandError::Unsupported {
+        message: "Memory caps need Linux with cgroup v2".into(),
+    })
+}
+
+#[tauri::command]
+pub fn remove_memory_cap(
+    app: AppHandle,
+    unit: String,
+    system: State<'_, SharedSystem>,
+) -> Result<(), CommandError> {
+    let cap = read_caps(&system.refreshed())
+        .into_iter()
+        .find(|c| c.unit == unit)
+        .ok_or_else(|| CommandError::NotFound {
+            message: "Cap not found — its processes may have exited".into(),
+        })?;
+    let result = uncap(&cap);
+    let pid = cap.pids.first().copied().unwrap_or(0);
+    let name = cap.names.first().cloned().unwrap_or_default();
+    audit::record_result(&app, "uncap", pid, &name, &result, "Memory cap lifted");
+    result
+}
+
+#[cfg(all(test, target_os = "linux"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn recognizes_cap_scopes() {
+        let capped = "0::/user.slice/user-1000.slice/user@1000.service/app.slice/system-cleaner-cap-1700000000123456789.scope\n";
+        assert!(is_cap_cgroup(capped));
+        assert!(is_cap_cgroup(
+            "0::/system.slice/system-cleaner-cap-1.scope\n"
+        ));
+        assert!(!is_cap_cgroup(
+            "0::/user.slice/user-1000.slice/user@1000.service/app.slice/app-gnome-firefox-1234.scope\n"
+        ));
+        // the app's own scope, or a slice named like one, isn't a cap
+        assert!(!is_cap_cgroup(
+            "0::/user.slice/system-cleaner-cap-1.scope/sub\n"
+        ));
+        assert!(!is_cap_cgroup("0::/system-cleaner-cap-1.slice\n"));
+        assert!(!is_cap_cgroup(""));
+    }
+}
@@ -1,7 +1,8 @@
 use serde::Serialize;
 use std::fs;
+use std::io::{self, Write};
 use std::path::Path;
-use std::process::Command;
+use std::process::{Command, Output, Stdio};
 
 #[derive(Debug, Serialize)]
 pub struct CleanupResult {
@@ -72,6 +73,130 @@ fn dir_size_fast(path: &Path) -> u64 {
     total
 }
 
+pub fn run_tool(program: &str, args: &[&str]) -> (bool, String) {
+    match Command::new(program).args(args).output() {
+        Ok(output) => {
+            let stdout = String::from_utf8_lossy(&output.stdout);
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            let text = format!("{}\n{}", stdout.trim(), stderr.trim());
+            (output.status.success(), text.trim().to_string())
+        }
+        Err(e) => (false, format!("Failed to run {program}: {e}")),
+    }
+}
+
+#[cfg(unix)]
+pub fn is_elevated() -> bool {
+    unsafe { libc::geteuid() == 0 }
+}
+
+#[cfg(windows)]
+pub fn is_elevated() -> bool {
+    use windows::Win32::Foundation::{CloseHandle, HANDLE};
+    use windows::Win32::Security::{
+        GetTokenInformation, TokenElevation, TOKEN_ELEVATION, TOKEN_QUERY,
+    };
+    use windows::Win32::System::Threading::{GetCurrentProcess, OpenProcessToken};
+
+    let mut token = HANDLE::default();
+    let mut elevation = TOKEN_ELEVATION::default();
+    let mut len = 0u32;
+    unsafe {
+        if OpenProcessToken(GetCurrentProcess(), TOKEN_QUERY, &mut token).is_err() {
+            return false;
+        }
+        let ok = GetTokenInformation(
+            token,
+            TokenElevation,
+            Some(&mut elevation as *mut TOKEN_ELEVATION as *mut std::ffi::c_void),
+            std::mem::size_of::<TOKEN_ELEVATION>() as u32,
+            &mut len,
+        )
+        .is_ok();
+        let _ = CloseHandle(token);
+        ok && elevation.TokenIsElevated != 0
+    }
+}
+
+#[cfg(not(any(unix, windows)))]
+pub fn is_elevated() -> bool {
+    false
+}
+
+// Runs a command that needs root through pkexec, so the user gets a polkit
+// prompt instead of a silent permission failure.
+pub fn run_privileged(program: &str, args: &[&str]) -> (bool, String) {
+    run_privileged_with_input(program, args, &[])
+}
+
+// Same as run_privileged, with `input` fed to the command's stdin. Long path
+// lists go this way so they take one call, and so one prompt.
+pub fn run_privileged_with_input(program: &str, args: &[&str], input: &[u8]) -> (bool, String) {
+    let direct = is_elevated() || cfg!(not(target_os = "linux"));
+    let mut command = if direct {
+        Command::new(program)
+    } else {
+        let mut pkexec = Command::new("pkexec");
+        pkexec.arg(program);
+        pkexec
+    };
+    command.args(args);
+
+    let output = match output_with_input(command, input) {
+        Ok(o) => o,
+        Err(e) if direct => return (false, format!("Failed to run {program}: {e}")),
+        Err(_) => {
+            return (
+                false,
+                format!("{program} needs administrator rights and pkexec is not installed"),
+            )
+        }
+    };
+    match output.status.code() {
+        Some(126) if !direct => (false, "Administrator authorization was cancelled".into()),
+        Some(127) if !direct => (false, "Not authorized to run as administrator".into()),
+        _ => {
+            let stdout = String::from_utf8_lossy(&output.stdout);
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            let text = format!("{}\n{}", stdout.trim(), stderr.trim());
+            (output.status.success(), text.trim().to_string())
+        }
+    }
+}
+
+fn output_with_input(mut command: Command, input: &[u8]) -> io::Result<Output> {
+    let mut child = command
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()?;
+    // A cancelled prompt closes the pipe early; the exit code says why
+    if let Some(mut stdin) = child.stdin.take() {
+        let _ = stdin.write_all(input);
+    }
+    child.wait_with_output()
+}
+
+pub fn delete_dir_contents_privileged(path: &Path) -> (u64, String) {
+    if is_elevated() || cfg!(not(target_os = "linux")) {
+        return delete_dir_contents(path);
+    }
+    if !path.exists() {
+        return (0, "Path not found".into());
+    }
+
+    let before = dir_size_fast(path);
+    let path_str = path.to_string_lossy();
+    let (success, output) =
+        run_privileged("find", &[path_str.as_ref(), "-mindepth", "1", "-delete"]);
+    let freed = before.saturating_sub(dir_size_fast(path));
+    if success {
+        (freed, "Cleaned successfully".into())
+    } else {
+        (freed, format!("Failed: {output}"))
+    }
+}
+
 fn clean_recycle_bin() -> (u64, String) {
     let output = Command::new("powershell")
         .args([
@@ -87,7 +212,7 @@ fn clean_recycle_bin() -> (u64, String) {
     }
 }
 
-#[tauri::command]
+#[tauri::command(async)]
 pub fn clean_items(item_ids: Vec<String>, item_paths: Vec<String>) -> CleanupSummary {
     let mut results = Vec::new();
     let mut total_freed = 0u64;
@@ -95,6 +220,19 @@ pub fn clean_items(item_ids: Vec<String>, item_paths: Vec<String>) -> CleanupSum
     for (id, path) in item_ids.iter().zip(item_paths.iter()) {
         let (freed, message) = match id.as_str() {
             "recycle_bin" => clean_recycle_bin(),
+            "log_compress" => super::logs::compress_old_logs(),
+            "log_prune" => super::logs::prune_rotated_logs(),
+            "journal_vacuum_time" => super::systemd::vacuum_journal_by_time(),
+            "journal_vacuum_size" => super::systemd::vacuum_journal_by_size(),
+            "thumbnail_orphans" => super::thumbnails::clean_orphaned_thumbnails(),
+            "thumbnail_current" => super::thumbnails::clean_current_thumbnails(),
+            "tmpfs_stale" => super::tmpfs::clean_stale_files(),
+            "coredumps" => super::systemd::clean_coredumps(),
+            "apt_cache" | "dnf_cache" | "pacman_cache" | "zypper_cache" => {
+                super::pkgcache::clean_package_cache(id)
+            }
+            "flatpak_unused" => super::flatpak::uninstall_unused(),
+            "snap_disabled" => super::snap::remove_disabled_revisions(),
             "memory_dump" => {
                 let p = Path::new(path);
                 let size = p.metadata().map(|m| m.len()).unwrap_or(0);
@@ -104,13 +242,9 @@ pub fn clean_items(item_ids: Vec<String>, item_paths: Vec<String>) -> CleanupSum
                 }
             }
             "windows_update" => {
-                let _ = Command::new("net")
-                    .args(["stop", "wuauserv"])
-                    .output();
+                let _ = Command::new("net").args(["stop", "wuauserv"]).output();
                 let result = delete_dir_contents(Path::new(path));
-                let _ = Command::new("net")
-                    .args(["start", "wuauserv"])
-                    .output();
+                let _ = Command::new("net").args(["start", "wuauserv"]).output();
                 result
             }
             _ => delete_dir_contents(Path::new(path)),
@@ -21,6 +21,7 @@ pub struct CleanableItem {
     pub path: String,
     pub risk: String, // "safe", "moderate", "advanced"
     pub category: String,
+    pub requires_elevation: bool,
 }
 
 #[derive(Debug, Serialize)]
@@ -65,6 +66,7 @@ fn scan_temp_files() -> Vec<CleanableItem> {
                 path: temp,
                 risk: "safe".into(),
                 category: "System".into(),
+                requires_elevation: false,
             });
         }
     }
@@ -80,6 +82,7 @@ fn scan_temp_files() -> Vec<CleanableItem> {
             path: win_temp.to_string_lossy().into(),
             risk: "safe".into(),
             category: "System".into(),
+            requires_elevation: true,
         });
     }
 
@@ -138,6 +141,7 @@ fn scan_browser_caches() -> Vec<CleanableItem> {
                 path: found_path,
                 risk: "safe".into(),
                 category: "Browsers".into(),
+                requires_elevation: false,
             });
         }
     }
@@ -159,6 +163,7 @@ fn scan_crash_dumps() -> Vec<CleanableItem> {
             path: dumps_path,
             risk: "safe".into(),
             category: "System".into(),
+            requires_elevation: false,
         });
     }
 
@@ -174,6 +179,7 @@ fn scan_crash_dumps() -> Vec<CleanableItem> {
                 path: mem_dump.to_string_lossy().into(),
                 risk: "safe".into(),
                 category: "System".into(),
+                requires_elevation: true,
             });
         }
     }
@@ -194,6 +200,7 @@ fn scan_windows_update() -> Vec<CleanableItem> {
             path: wu_path.to_string_lossy().into(),
             risk: "moderate".into(),
             category: "System".into(),
+            requires_elevation: true,
         });
     }
     items
@@ -212,6 +219,7 @@ fn scan_recycle_bin() -> Vec<CleanableItem> {
             path: rb_path.to_string_lossy().into(),
             risk: "safe".into(),
             category: "System".into(),
+            requires_elevation: false,
         });
     }
     items
@@ -241,6 +249,7 @@ fn scan_thumbnail_cache() -> Vec<CleanableItem> {
                 path: thumb_path,
                 risk: "safe".into(),
                 category: "System".into(),
+                requires_elevation: false,
             });
         }
     }
@@ -297,6 +306,7 @@ fn scan_dev_caches() -> Vec<CleanableItem> {
                     path: path.to_string_lossy().into(),
                     risk: "moderate".into(),
                     category: "Developer".into(),
+                    requires_elevation: false,
                 });
             }
         }
@@ -305,44 +315,6 @@ fn scan_dev_caches() -> Vec<CleanableItem> {
     items
 }
 
-fn scan_log_files() -> Vec<CleanableItem> {
-    let mut items = Vec::new();
-    let local = env::var("LOCALAPPDATA").unwrap_or_default();
-
-    let log_dirs = vec![
-        Path::new("C:\\Windows\\Logs").to_path_buf(),
-        PathBuf::from(format!("{local}\\Temp")),
-    ];
-
-    let mut total_log_size = 0u64;
-    for dir in &log_dirs {
-        if dir.exists() {
-            if let Ok(entries) = fs::read_dir(dir) {
-                for entry in entries.flatten() {
-                    let name = entry.file_name().to_string_lossy().to_string();
-                    if name.ends_with(".log") || name.ends_with(".etl") {
-                        total_log_size += entry.metadata().map(|m| m.len()).unwrap_or(0);
-                    }
-                }
-            }
-        }
-    }
-
-    if total_log_size > 1_000_000 {
-        items.push(CleanableItem {
-            id: "log_files".into(),
-            name: "Old Log Files".into(),
-            description: "System and application log files".into(),
-            size_bytes: total_log_size,
-            path: "Various locations".into(),
-            risk: "moderate".into(),
-            category: "System".into(),
-        });
-    }
-
-    items
-}
-
 #[tauri::command]
 pub fn scan_disk() -> DiskScanResult {
     let mut items = Vec::new();
@@ -351,10 +323,17 @@ pub fn scan_disk() -> DiskScanResult {
     items.extend(scan_recycle_bin());
     items.extend(scan_browser_caches());
     items.extend(scan_crash_dumps());
+    items.extend(super::systemd::scan_coredumps());
+    items.extend(super::systemd::scan_journal());
     items.extend(scan_windows_update());
+    items.extend(super::pkgcache::scan_package_caches());
     items.extend(scan_thumbnail_cache());
+    items.extend(super::thumbnails::scan_thumbnail_items());
     items.extend(scan_dev_caches());
-    items.extend(scan_log_files());
+    items.extend(super::flatpak::scan_flatpak());
+    items.extend(super::snap::scan_snap());
+    items.extend(super::logs::scan_log_items());
+    items.extend(super::tmpfs::scan_tmpfs_items());
 
     items.sort_by(|a, b| b.size_bytes.cmp(&a.size_bytes));
 
@@ -366,6 +345,7 @@ pub fn scan_disk() -> DiskScanResult {
     }
 }
 
+#[cfg(windows)]
 #[tauri::command]
 pub fn get_drive_info() -> Vec<DriveInfo> {
     let mut drives = Vec::new();
@@ -389,6 +369,25 @@ pub fn get_drive_info() -> Vec<DriveInfo> {
     drives
 }
 
+#[cfg(not(windows))]
+#[tauri::command]
+pub fn get_drive_info() -> Vec<DriveInfo> {
+    let disks = sysinfo::Disks::new_with_refreshed_list();
+    disks
+        .list()
+        .iter()
+        .filter(|d| d.total_space() > 0)
+        .map(|d| DriveInfo {
+            letter: d.mount_point().to_string_lossy().into(),
+            label: d.name().to_string_lossy().into(),
+            total_bytes: d.total_space(),
+            free_bytes: d.available_space(),
+            used_bytes: d.total_space().saturating_sub(d.available_space()),
+        })
+        .collect()
+}
+
+#[cfg(windows)]
 fn fs2_free_space(root: &str) -> Result<(u64, u64), String> {
     use windows::core::PCSTR;
     use windows::Win32::Storage::FileSystem::GetDiskFreeSpaceExA;
@@ -411,6 +410,7 @@ fn fs2_free_space(root: &str) -> Result<(u64, u64), String> {
     Ok((total_bytes, free_bytes_available))
 }
 
+#[cfg(windows)]
 fn get_volume_label(root: &str) -> String {
     use windows::core::PCSTR;
     use windows::Win32::Storage::FileSystem::GetVolumeInformationA;
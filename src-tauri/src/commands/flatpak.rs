@@ -0,0 +1,487 @@
+use serde::Serialize;
+use std::cmp::Reverse;
+use std::collections::{HashMap, HashSet};
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use super::cleanup::{run_tool, CleanupResult};
+use super::disk::CleanableItem;
+
+#[derive(Debug, Serialize, Clone)]
+pub struct FlatpakRuntime {
+    pub reference: String,
+    pub installation: String, // "system", "user"
+    pub size_bytes: u64,
+    pub used: bool,
+    pub used_by: Vec<String>,
+}
+
+struct Installation {
+    name: &'static str,
+    path: PathBuf,
+}
+
+struct Ref {
+    id: String,
+    arch: String,
+    branch: String,
+    path: PathBuf,
+}
+
+impl Ref {
+    fn triple(&self) -> String {
+        format!("{}/{}/{}", self.id, self.arch, self.branch)
+    }
+}
+
+fn installations() -> Vec<Installation> {
+    let mut list = vec![Installation {
+        name: "system",
+        path: PathBuf::from("/var/lib/flatpak"),
+    }];
+    let data_home = env::var("XDG_DATA_HOME")
+        .ok()
+        .filter(|v| !v.is_empty())
+        .map(PathBuf::from)
+        .or_else(|| {
+            env::var("HOME")
+                .ok()
+                .map(|h| PathBuf::from(h).join(".local/share"))
+        });
+    if let Some(data_home) = data_home {
+        list.push(Installation {
+            name: "user",
+            path: data_home.join("flatpak"),
+        });
+    }
+    list.into_iter().filter(|i| i.path.is_dir()).collect()
+}
+
+fn subdirs(path: &Path) -> Vec<(String, PathBuf)> {
+    let mut dirs = Vec::new();
+    if let Ok(entries) = fs::read_dir(path) {
+        for entry in entries.flatten() {
+            if entry.file_type().map(|t| t.is_dir()).unwrap_or(false) {
+                dirs.push((
+                    entry.file_name().to_string_lossy().to_string(),
+                    entry.path(),
+                ));
+            }
+        }
+    }
+    dirs
+}
+
+// <installation>/{app,runtime}/<id>/<arch>/<branch>/active
+fn list_refs(inst: &Installation, kind: &str) -> Vec<Ref> {
+    let mut refs = Vec::new();
+    for (id, id_path) in subdirs(&inst.path.join(kind)) {
+        for (arch, arch_path) in subdirs(&id_path) {
+            for (branch, branch_path) in subdirs(&arch_path) {
+                if branch_path.join("active").exists() {
+                    refs.push(Ref {
+                        id: id.clone(),
+                        arch: arch.clone(),
+                        branch,
+                        path: branch_path,
+                    });
+                }
+            }
+        }
+    }
+    refs
+}
+
+// The `runtime=` and `sdk=` refs in a deploy's metadata, from the
+// [Application] or [Runtime] group.
+fn parse_metadata_refs(text: &str) -> Vec<String> {
+    let mut refs = Vec::new();
+    let mut section = "";
+    for line in text.lines() {
+        let line = line.trim();
+        if let Some(name) = line.strip_prefix('[').and_then(|l| l.strip_suffix(']')) {
+            section = name;
+        } else if section == "Application" || section == "Runtime" {
+            if let Some((key, value)) = line.split_once('=') {
+                if key.trim() == "runtime" || key.trim() == "sdk" {
+                    refs.push(value.trim().to_string());
+                }
+            }
+        }
+    }
+    refs
+}
+
+fn metadata_refs(r: &Ref) -> Vec<String> {
+    fs::read_to_string(r.path.join("active").join("metadata"))
+        .map(|text| parse_metadata_refs(&text))
+        .unwrap_or_default()
+}
+
+// `flatpak pin` prints one pattern per line, "runtime/<id>/<arch>/<branch>"
+// with arch and branch optional. Only the id is kept, so a pin covers every
+// branch of it.
+fn parse_pins(text: &str) -> Vec<String> {
+    text.lines()
+        .filter_map(|line| line.trim().strip_prefix("runtime/"))
+        .filter_map(|pattern| pattern.split('/').next())
+        .filter(|id| !id.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+// Read-only; None if flatpak couldn't be run.
+fn pinned_runtimes(inst: &Installation) -> Option<Vec<String>> {
+    let output = Command::new("flatpak")
+        .args(["pin", &format!("--{}", inst.name)])
+        .env("LC_ALL", "C")
+        .output()
+        .ok()?;
+    output
+        .status
+        .success()
+        .then(|| parse_pins(&String::from_utf8_lossy(&output.stdout)))
+}
+
+// Triples of the runtimes still needed: any runtime or sdk an installed app
+// or runtime names, anything pinned, and the extensions (.Locale, .Debug,
+// GL drivers, codecs) of the apps and of those runtimes, whatever their
+// branch. This keeps more than `flatpak uninstall --unused` would, never less.
+fn needed_runtimes(
+    apps: &[(String, Vec<String>)],
+    runtimes: &[(String, String, Vec<String>)],
+    pinned: &[String],
+) -> HashSet<String> {
+    let mut needed: HashSet<String> = apps
+        .iter()
+        .flat_map(|(_, refs)| refs)
+        .chain(
+            runtimes
+                .iter()
+                .flat_map(|(_, triple, refs)| refs.iter().filter(move |r| *r != triple)),
+        )
+        .cloned()
+        .collect();
+    needed.extend(
+        runtimes
+            .iter()
+            .filter(|(id, _, _)| pinned.contains(id))
+            .map(|(_, triple, _)| triple.clone()),
+    );
+
+    let parents: Vec<String> = apps
+        .iter()
+        .map(|(id, _)| format!("{id}."))
+        .chain(
+            runtimes
+                .iter()
+                .filter(|(_, triple, _)| needed.contains(triple))
+                .map(|(id, _, _)| format!("{id}.")),
+        )
+        .collect();
+    needed.extend(
+        runtimes
+            .iter()
+            .filter(|(id, _, _)| parents.iter().any(|p| id.starts_with(p.as_str())))
+            .map(|(_, triple, _)| triple.clone()),
+    );
+    needed
+}
+
+// Unlike disk::dir_size this doesn't follow symlinks, so the `active` link
+// next to each deployment isn't counted twice.
+fn dir_size(path: &Path) -> u64 {
+    let mut total = 0u64;
+    if let Ok(entries) = fs::read_dir(path) {
+        for entry in entries.flatten() {
+            let file_type = match entry.file_type() {
+                Ok(t) => t,
+                Err(_) => continue,
+            };
+            if file_type.is_file() {
+                total += entry.metadata().map(|m| m.len()).unwrap_or(0);
+            } else if file_type.is_dir() {
+                total += dir_size(&entry.path());
+            }
+        }
+    }
+    total
+}
+
+fn list_runtimes() -> Vec<FlatpakRuntime> {
+    let insts = installations();
+
+    // Apps in either installation can use runtimes from both.
+    let apps: Vec<(String, Vec<String>)> = insts
+        .iter()
+        .flat_map(|inst| list_refs(inst, "app"))
+        .map(|app| {
+            let refs = metadata_refs(&app);
+            (app.id, refs)
+        })
+        .collect();
+    let mut used_by: HashMap<String, Vec<String>> = HashMap::new();
+    for (id, refs) in &apps {
+        for r in refs {
+            used_by.entry(r.clone()).or_default().push(id.clone());
+        }
+    }
+
+    let mut list = Vec::new();
+    for inst in &insts {
+        let refs = list_refs(inst, "runtime");
+        let runtimes: Vec<(String, String, Vec<String>)> = refs
+            .iter()
+            .map(|r| (r.id.clone(), r.triple(), metadata_refs(r)))
+            .collect();
+        // If flatpak can't be run, nothing is offered for removal
+        let needed = pinned_runtimes(inst).map(|pins| needed_runtimes(&apps, &runtimes, &pins));
+        for r in refs {
+            let triple = r.triple();
+            list.push(FlatpakRuntime {
+                used: needed.as_ref().is_none_or(|n| n.contains(&triple)),
+                used_by: used_by.get(&triple).cloned().unwrap_or_default(),
+                reference: format!("runtime/{triple}"),
+                installation: inst.name.into(),
+                size_bytes: dir_size(&r.path),
+            });
+        }
+    }
+
+    list.sort_by_key(|r| Reverse(r.size_bytes));
+    list
+}
+
+pub fn scan_flatpak() -> Vec<CleanableItem> {
+    let mut items = Vec::new();
+    let unused: Vec<FlatpakRuntime> = list_runtimes().into_iter().filter(|r| !r.used).collect();
+    let size: u64 = unused.iter().map(|r| r.size_bytes).sum();
+
+    if size > 0 {
+        items.push(CleanableItem {
+            id: "flatpak_unused".into(),
+            name: "Unused Flatpak Runtimes".into(),
+            description: format!(
+                "{} runtimes no installed app needs — removed with flatpak uninstall",
+                unused.len()
+            ),
+            size_bytes: size,
+            path: installations()
+                .iter()
+                .map(|i| i.path.to_string_lossy().to_string())
+                .collect::<Vec<_>>()
+                .join(", "),
+            risk: "moderate".into(),
+            category: "Applications".into(),
+            requires_elevation: false,
+        });
+    }
+
+    items
+}
+
+// Removes exactly the runtimes the scan lists, rather than
+// `flatpak uninstall --unused`, whose idea of unused may be wider.
+pub fn uninstall_unused() -> (u64, String) {
+    let unused: Vec<FlatpakRuntime> = list_runtimes().into_iter().filter(|r| !r.used).collect();
+
+    let mut freed = 0u64;
+    let mut messages = Vec::new();
+    let mut ok = true;
+    for inst in installations() {
+        let runtimes: Vec<&FlatpakRuntime> = unused
+            .iter()
+            .filter(|r| r.installation == inst.name)
+            .collect();
+        if runtimes.is_empty() {
+            continue;
+        }
+        let scope = format!("--{}", inst.name);
+        let mut args = vec!["uninstall", &scope, "--noninteractive", "-y", "--"];
+        args.extend(runtimes.iter().map(|r| r.reference.as_str()));
+        let (success, output) = run_tool("flatpak", &args);
+        if success {
+            freed += runtimes.iter().map(|r| r.size_bytes).sum::<u64>();
+        }
+        ok &= success;
+        messages.push(output);
+    }
+
+    let summary = if ok {
+        "Cleaned successfully"
+    } else {
+        "Cleaned with errors"
+    };
+    messages.insert(0, summary.into());
+    (freed, messages.join("\n"))
+}
+
+#[tauri::command]
+pub fn get_flatpak_runtimes() -> Vec<FlatpakRuntime> {
+    list_runtimes()
+}
+
+// Only a runtime the scan lists as unused in that installation may be
+// removed; anything else, an option like "--all" included, is refused.
+fn removable<'a>(
+    runtimes: &'a [FlatpakRuntime],
+    installation: &str,
+    reference: &str,
+) -> Result<&'a FlatpakRuntime, &'static str> {
+    let runtime = runtimes
+        .iter()
+        .find(|r| r.installation == installation && r.reference == reference)
+        .ok_or("Not an installed flatpak runtime")?;
+    if runtime.used {
+        return Err("Runtime is still in use");
+    }
+    Ok(runtime)
+}
+
+#[tauri::command(async)]
+pub fn remove_flatpak_runtime(installation: String, reference: String) -> CleanupResult {
+    let refuse = |message: &str| CleanupResult {
+        id: reference.clone(),
+        name: reference.clone(),
+        success: false,
+        bytes_freed: 0,
+        message: message.into(),
+    };
+    let scope = match installation.as_str() {
+        "system" => "--system",
+        "user" => "--user",
+        _ => return refuse("Unknown flatpak installation"),
+    };
+    let runtimes = list_runtimes();
+    let size = match removable(&runtimes, &installation, &reference) {
+        Ok(runtime) => runtime.size_bytes,
+        Err(message) => return refuse(message),
+    };
+
+    let (success, message) = run_tool(
+        "flatpak",
+        &[
+            "uninstall",
+            scope,
+            "--noninteractive",
+            "-y",
+            "--",
+            &reference,
+        ],
+    );
+    CleanupResult {
+        id: reference.clone(),
+        name: reference,
+        success,
+        bytes_freed: if success { size } else { 0 },
+        message,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn runtime(id: &str, branch: &str, refs: &[&str]) -> (String, String, Vec<String>) {
+        (
+            id.to_string(),
+            format!("{id}/x86_64/{branch}"),
+            refs.iter().map(|r| r.to_string()).collect(),
+        )
+    }
+
+    #[test]
+    fn reads_runtime_and_sdk_from_metadata() {
+        let app = "\
+[Application]
+name=org.gnome.Maps
+runtime=org.gnome.Platform/x86_64/46
+sdk=org.gnome.Sdk/x86_64/46
+command=gnome-maps
+
+[Extension org.gnome.Maps.Locale]
+directory=share/runtime/locale
+";
+        assert_eq!(
+            parse_metadata_refs(app),
+            ["org.gnome.Platform/x86_64/46", "org.gnome.Sdk/x86_64/46"]
+        );
+
+        let sdk = "[Runtime]\nname=org.gnome.Sdk\nruntime=org.gnome.Platform/x86_64/46\n";
+        assert_eq!(parse_metadata_refs(sdk), ["org.gnome.Platform/x86_64/46"]);
+        assert!(parse_metadata_refs("[Extension x]\nruntime=a/b/c\n").is_empty());
+    }
+
+    #[test]
+    fn reads_pin_patterns() {
+        let text =
+            "runtime/org.gnome.Platform/x86_64/44\nruntime/org.kde.Platform\napp/org.x.App\n\n";
+        assert_eq!(parse_pins(text), ["org.gnome.Platform", "org.kde.Platform"]);
+        assert!(parse_pins("").is_empty());
+    }
+
+    #[test]
+    fn keeps_what_apps_runtimes_and_pins_need() {
+        let apps = vec![(
+            "org.gnome.Maps".to_string(),
+            vec!["org.gnome.Platform/x86_64/46".to_string()],
+        )];
+        let runtimes = vec![
+            runtime("org.gnome.Platform", "46", &[]),
+            runtime("org.gnome.Platform", "45", &[]),
+            runtime("org.gnome.Platform.Locale", "46", &[]),
+            runtime("org.gnome.Platform.Locale", "45", &[]),
+            runtime("org.gnome.Maps.Locale", "stable", &[]),
+            // the branch of a driver or codec extension needn't match its parent's
+            runtime("org.gnome.Platform.openh264", "2.2.0", &[]),
+            // an installed SDK keeps its platform
+            runtime("org.kde.Sdk", "6.7", &["org.kde.Platform/x86_64/6.7"]),
+            runtime("org.kde.Platform", "6.7", &[]),
+            runtime("org.freedesktop.Platform", "23.08", &[]),
+            runtime("org.freedesktop.Platform.GL.default", "23.08", &[]),
+            runtime("org.gtk.Gtk3theme.Adwaita-dark", "3.22", &[]),
+        ];
+        let pinned = vec!["org.gtk.Gtk3theme.Adwaita-dark".to_string()];
+
+        let needed = needed_runtimes(&apps, &runtimes, &pinned);
+        let mut unused: Vec<&str> = runtimes
+            .iter()
+            .map(|(_, triple, _)| triple.as_str())
+            .filter(|t| !needed.contains(*t))
+            .collect();
+        unused.sort();
+        assert_eq!(
+            unused,
+            [
+                "org.freedesktop.Platform.GL.default/x86_64/23.08",
+                "org.freedesktop.Platform/x86_64/23.08",
+                "org.gnome.Platform/x86_64/45",
+                "org.kde.Sdk/x86_64/6.7",
+            ]
+        );
+    }
+
+    #[test]
+    fn removes_only_listed_unused_runtimes() {
+        let entry = |installation: &str, reference: &str, used: bool| FlatpakRuntime {
+            reference: reference.into(),
+            installation: installation.into(),
+            size_bytes: 100,
+            used,
+            used_by: Vec::new(),
+        };
+        let runtimes = vec![
+            entry("system", "runtime/org.gnome.Platform/x86_64/45", false),
+            entry("system", "runtime/org.gnome.Platform/x86_64/46", true),
+            entry("user", "runtime/org.kde.Platform/x86_64/6.7", false),
+        ];
+
+        assert!(removable(&runtimes, "system", "runtime/org.gnome.Platform/x86_64/45").is_ok());
+        assert!(removable(&runtimes, "system", "runtime/org.gnome.Platform/x86_64/46").is_err());
+        assert!(removable(&runtimes, "system", "runtime/org.kde.Platform/x86_64/6.7").is_err());
+        assert!(removable(&runtimes, "system", "--all").is_err());
+        assert!(removable(&runtimes, "user", "org.kde.Platform").is_err());
+    }
+}
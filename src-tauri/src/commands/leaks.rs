@@ -0,0 +1,126 @@
+use serde::Serialize;
+use sysinfo::{Pid, System};
+
+use super::sampler::{ProcessWatch, SamplerConfig};
+
+const MIN_POINTS: usize = 10;
+const MIN_FIT: f64 = 0.8; // R² of the trend line; below this growth isn't steady
+
+#[derive(Debug, Serialize, Clone)]
+pub struct LeakSuspect {
+    pub pid: u32,
+    pub name: String,
+    pub start_bytes: u64,
+    pub current_bytes: u64,
+    pub slope_mb_per_hour: f64,
+    pub duration_secs: u64,
+    pub fit: f64,
+}
+
+// Least-squares line through (seconds, bytes): the slope in bytes per second
+// and R², how much of the variation the line explains.
+fn fit_line(points: &[(f64, f64)]) -> Option<(f64, f64)> {
+    if points.len() < 2 {
+        return None;
+    }
+    let n = points.len() as f64;
+    let mean_x = points.iter().map(|p| p.0).sum::<f64>() / n;
+    let mean_y = points.iter().map(|p| p.1).sum::<f64>() / n;
+
+    let (mut sxx, mut sxy, mut syy) = (0.0, 0.0, 0.0);
+    for &(x, y) in points {
+        sxx += (x - mean_x) * (x - mean_x);
+        sxy += (x - mean_x) * (y - mean_y);
+        syy += (y - mean_y) * (y - mean_y);
+    }
+    if sxx == 0.0 || syy == 0.0 {
+        return None;
+    }
+    Some((sxy / sxx, sxy * sxy / (sxx * syy)))
+}
+
+// Fits a trend to each process's memory over the leak window and flags
+// those growing steadily faster than the configured rate. Only processes
+// that are still running are reported.
+pub fn find_leak_suspects(
+    watch: &ProcessWatch,
+    config: &SamplerConfig,
+    sys: &System,
+) -> Vec<LeakSuspect> {
+    let mut suspects: Vec<LeakSuspect> = watch
+        .rss_series()
+        .iter()
+        .filter_map(|(&(pid, start_time), series)| {
+            let points = &series.points;
+            let &(first, start_bytes) = points.front()?;
+            let &(last, _) = points.back()?;
+            let duration_secs = last - first;
+            if points.len() < MIN_POINTS || duration_secs < config.leak_window_secs / 2 {
+                return None;
+            }
+
+            let process = sys.process(Pid::from_u32(pid))?;
+            if process.start_time() != start_time {
+                return None;
+            }
+
+            let xy: Vec<(f64, f64)> = points
+                .iter()
+                .map(|&(t, bytes)| ((t - first) as f64, bytes as f64))
+                .collect();
+            let (slope, fit) = fit_line(&xy)?;
+            let slope_mb_per_hour = slope * 3600.0 / 1_048_576.0;
+            if slope_mb_per_hour < config.leak_threshold_mb_per_hour || fit < MIN_FIT {
+                return None;
+            }
+
+            Some(LeakSuspect {
+                pid,
+                name: series.name.clone(),
+                start_bytes,
+                current_bytes: process.memory(),
+                slope_mb_per_hour,
+                duration_secs,
+                fit,
+            })
+        })
+        .collect();
+
+    suspects.sort_by(|a, b| b.slope_mb_per_hour.total_cmp(&a.slope_mb_per_hour));
+    suspects
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fits_a_perfect_line() {
+        let points: Vec<(f64, f64)> = (0..10).map(|x| (x as f64, 3.0 * x as f64 + 7.0)).collect();
+        let (slope, fit) = fit_line(&points).unwrap();
+        assert!((slope - 3.0).abs() < 1e-9);
+        assert!((fit - 1.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn noisy_growth_fits_worse() {
+        let points = [(0.0, 0.0), (1.0, 10.0), (2.0, 0.0), (3.0, 10.0), (4.0, 5.0)];
+        let (slope, fit) = fit_line(&points).unwrap();
+        assert!(slope > 0.0);
+        assert!(fit < MIN_FIT);
+    }
+
+    #[test]
+    fn no_fit_without_variation() {
+        // flat memory: R² is 0/0
+        assert_eq!(fit_line(&[(0.0, 5.0), (60.0, 5.0), (120.0, 5.0)]), None);
+        // all at one instant: no slope
+        assert_eq!(fit_line(&[(60.0, 1.0), (60.0, 2.0)]), None);
+    }
+
+    #[test]
+    fn no_fit_below_two_points() {
+        assert_eq!(fit_line(&[]), None);
+        assert_eq!(fit_line(&[(0.0, 1.0)]), None);
+    }
+}
@@ -0,0 +1,773 @@
+use serde::{Deserialize, Serialize};
+use std::cmp::Reverse;
+use std::collections::{HashMap, HashSet};
+use std::env;
+use std::fs::{self, File};
+use std::io::{self, Read, Write};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+
+use super::cleanup::{CleanupResult, CleanupSummary};
+use super::disk::CleanableItem;
+
+const SAMPLE_BYTES: u64 = 256 * 1024;
+const MIN_COMPRESS_BYTES: u64 = 4096;
+const RECENT_WRITE_GRACE: Duration = Duration::from_secs(15 * 60);
+const MAX_DEPTH: u32 = 3;
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum LogCompression {
+    Gzip,
+    Zstd,
+}
+
+impl LogCompression {
+    fn extension(self) -> &'static str {
+        match self {
+            LogCompression::Gzip => "gz",
+            LogCompression::Zstd => "zst",
+        }
+    }
+
+    fn label(self) -> &'static str {
+        match self {
+            LogCompression::Gzip => "gzip",
+            LogCompression::Zstd => "zstd",
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct LogRetentionPolicy {
+    pub compress_after_days: u64,
+    pub keep_per_family: usize,
+    pub compression: LogCompression,
+}
+
+impl Default for LogRetentionPolicy {
+    fn default() -> Self {
+        LogRetentionPolicy {
+            compress_after_days: 7,
+            keep_per_family: 5,
+            compression: LogCompression::Gzip,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct LogFileAction {
+    pub path: String,
+    pub family: String,
+    pub size_bytes: u64,
+    pub age_days: u64,
+    pub action: String, // "keep", "compress", "delete", "in_use"
+    pub estimated_savings: u64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct LogRetentionScan {
+    pub files: Vec<LogFileAction>,
+    pub compress_count: u32,
+    pub delete_count: u32,
+    pub in_use_count: u32,
+    pub compress_savings_bytes: u64,
+    pub delete_savings_bytes: u64,
+}
+
+struct LogDir {
+    path: PathBuf,
+    // Dedicated log directories may hold rotated files without a .log suffix
+    // (syslog.2.gz); shared ones like %TEMP% only contribute *.log / *.etl.
+    dedicated: bool,
+}
+
+struct LogName {
+    family: String,
+    compressed: bool,
+    rotated: bool,
+    has_log_ext: bool,
+}
+
+struct Candidate {
+    path: PathBuf,
+    name: LogName,
+    size: u64,
+    modified: SystemTime,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Action {
+    Keep,
+    Compress,
+    Delete,
+    InUse,
+}
+
+impl Action {
+    fn as_str(self) -> &'static str {
+        match self {
+            Action::Keep => "keep",
+            Action::Compress => "compress",
+            Action::Delete => "delete",
+            Action::InUse => "in_use",
+        }
+    }
+}
+
+struct PlannedFile {
+    path: PathBuf,
+    family: String,
+    size: u64,
+    age_days: u64,
+    action: Action,
+    savings: u64,
+}
+
+fn log_dirs() -> Vec<LogDir> {
+    let mut dirs = Vec::new();
+
+    #[cfg(windows)]
+    {
+        dirs.push(LogDir {
+            path: PathBuf::from("C:\\Windows\\Logs"),
+            dedicated: true,
+        });
+        if let Ok(local) = env::var("LOCALAPPDATA") {
+            dirs.push(LogDir {
+                path: PathBuf::from(format!("{local}\\Temp")),
+                dedicated: false,
+            });
+        }
+    }
+
+    #[cfg(not(windows))]
+    {
+        dirs.push(LogDir {
+            path: PathBuf::from("/var/log"),
+            dedicated: true,
+        });
+        if let Ok(home) = env::var("HOME") {
+            dirs.push(LogDir {
+                path: PathBuf::from(home).join(".local").join("state"),
+                dedicated: false,
+            });
+        }
+    }
+
+    dirs
+}
+
+fn strip_suffix_ci<'a>(name: &'a str, suffix: &str) -> Option<&'a str> {
+    let split = name.len().checked_sub(suffix.len())?;
+    if name.is_char_boundary(split) && name[split..].eq_ignore_ascii_case(suffix) {
+        Some(&name[..split])
+    } else {
+        None
+    }
+}
+
+fn strip_rotation(name: &str) -> Option<&str> {
+    // foo.log.3
+    if let Some((base, n)) = name.rsplit_once('.') {
+        if !base.is_empty()
+            && !n.is_empty()
+            && n.len() <= 4
+            && n.bytes().all(|b| b.is_ascii_digit())
+        {
+            return Some(base);
+        }
+    }
+    // foo.log-20240131 (logrotate dateext)
+    if let Some((base, date)) = name.rsplit_once('-') {
+        if !base.is_empty() && date.len() == 8 && date.bytes().all(|b| b.is_ascii_digit()) {
+            return Some(base);
+        }
+    }
+    None
+}
+
+fn parse_log_name(name: &str) -> LogName {
+    let mut base = name;
+    let mut compressed = false;
+    for ext in [".gz", ".zst", ".xz", ".bz2"] {
+        if let Some(stripped) = strip_suffix_ci(base, ext) {
+            base = stripped;
+            compressed = true;
+            break;
+        }
+    }
+
+    let mut rotated = false;
+    if let Some(stripped) = strip_rotation(base) {
+        base = stripped;
+        rotated = true;
+    } else {
+        // foo.1.log
+        for ext in [".log", ".etl"] {
+            if let Some(inner) = strip_suffix_ci(base, ext).and_then(strip_rotation) {
+                return LogName {
+                    family: format!("{inner}{}", &base[base.len() - ext.len()..]),
+                    compressed,
+                    rotated: true,
+                    has_log_ext: true,
+                };
+            }
+        }
+    }
+
+    let has_log_ext =
+        strip_suffix_ci(base, ".log").is_some() || strip_suffix_ci(base, ".etl").is_some();
+
+    LogName {
+        family: base.to_string(),
+        compressed,
+        rotated,
+        has_log_ext,
+    }
+}
+
+fn collect_candidates(dir: &LogDir, path: &Path, depth: u32, out: &mut Vec<Candidate>) {
+    let entries = match fs::read_dir(path) {
+        Ok(e) => e,
+        Err(_) => return,
+    };
+
+    for entry in entries.flatten() {
+        let file_type = match entry.file_type() {
+            Ok(t) => t,
+            Err(_) => continue,
+        };
+        let p = entry.path();
+
+        if file_type.is_dir() {
+            if depth < MAX_DEPTH {
+                collect_candidates(dir, &p, depth + 1, out);
+            }
+            continue;
+        }
+        if !file_type.is_file() {
+            continue;
+        }
+
+        let name = parse_log_name(&entry.file_name().to_string_lossy());
+        let is_log = name.has_log_ext || (dir.dedicated && (name.rotated || name.compressed));
+        if !is_log {
+            continue;
+        }
+
+        if let Ok(meta) = entry.metadata() {
+            out.push(Candidate {
+                path: p,
+                name,
+                size: meta.len(),
+                modified: meta.modified().unwrap_or(SystemTime::UNIX_EPOCH),
+            });
+        }
+    }
+}
+
+#[cfg(target_os = "linux")]
+fn files_open_for_writing() -> HashSet<PathBuf> {
+    let mut open = HashSet::new();
+    let procs = match fs::read_dir("/proc") {
+        Ok(p) => p,
+        Err(_) => return open,
+    };
+
+    for proc_entry in procs.flatten() {
+        let is_pid = proc_entry
+            .file_name()
+            .to_string_lossy()
+            .bytes()
+            .all(|b| b.is_ascii_digit());
+        if !is_pid {
+            continue;
+        }
+
+        let fds = match fs::read_dir(proc_entry.path().join("fd")) {
+            Ok(f) => f,
+            Err(_) => continue,
+        };
+        for fd in fds.flatten() {
+            let target = match fs::read_link(fd.path()) {
+                Ok(t) if t.is_absolute() => t,
+                _ => continue,
+            };
+            let info = fs::read_to_string(proc_entry.path().join("fdinfo").join(fd.file_name()))
+                .unwrap_or_default();
+            let flags = info
+                .lines()
+                .find_map(|l| l.strip_prefix("flags:"))
+                .and_then(|f| u32::from_str_radix(f.trim(), 8).ok())
+                .unwrap_or(0);
+            // O_WRONLY | O_RDWR
+            if flags & 0o3 != 0 {
+                open.insert(target);
+            }
+        }
+    }
+
+    open
+}
+
+#[cfg(not(target_os = "linux"))]
+fn files_open_for_writing() -> HashSet<PathBuf> {
+    HashSet::new()
+}
+
+#[cfg(windows)]
+fn locked_for_writing(path: &Path) -> bool {
+    use std::os::windows::fs::OpenOptionsExt;
+    const FILE_SHARE_READ: u32 = 0x1;
+
+    // Refusing to share write access fails if any other handle is writing.
+    fs::OpenOptions::new()
+        .read(true)
+        .share_mode(FILE_SHARE_READ)
+        .open(path)
+        .is_err()
+}
+
+#[cfg(not(windows))]
+fn locked_for_writing(_path: &Path) -> bool {
+    false
+}
+
+fn is_in_use(candidate: &Candidate, open: &HashSet<PathBuf>, now: SystemTime) -> bool {
+    let recently_written = now
+        .duration_since(candidate.modified)
+        .map(|age| age < RECENT_WRITE_GRACE)
+        .unwrap_or(true);
+
+    recently_written || open.contains(&candidate.path) || locked_for_writing(&candidate.path)
+}
+
+fn compress_stream<R: Read, W: Write>(
+    codec: LogCompression,
+    mut reader: R,
+    writer: W,
+) -> io::Result<W> {
+    match codec {
+        LogCompression::Gzip => {
+            let mut encoder = flate2::write::GzEncoder::new(writer, flate2::Compression::default());
+            io::copy(&mut reader, &mut encoder)?;
+            encoder.finish()
+        }
+        LogCompression::Zstd => {
+            let mut encoder = zstd::stream::write::Encoder::new(writer, 3)?;
+            io::copy(&mut reader, &mut encoder)?;
+            encoder.finish()
+        }
+    }
+}
+
+struct CountingSink(u64);
+
+impl Write for CountingSink {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.0 += buf.len() as u64;
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+fn estimate_savings(path: &Path, size: u64, codec: LogCompression) -> u64 {
+    let file = match File::open(path) {
+        Ok(f) => f,
+        Err(_) => return 0,
+    };
+    let sampled = size.min(SAMPLE_BYTES);
+    if sampled == 0 {
+        return 0;
+    }
+
+    match compress_stream(codec, file.take(SAMPLE_BYTES), CountingSink(0)) {
+        Ok(sink) => {
+            let ratio = (sink.0 as f64 / sampled as f64).min(1.0);
+            (size as f64 * (1.0 - ratio)) as u64
+        }
+        Err(_) => 0,
+    }
+}
+
+fn plan_retention(policy: &LogRetentionPolicy) -> Vec<PlannedFile> {
+    let mut candidates = Vec::new();
+    for dir in log_dirs() {
+        collect_candidates(&dir, &dir.path, 0, &mut candidates);
+    }
+
+    let mut families: HashMap<(PathBuf, String), Vec<Candidate>> = HashMap::new();
+    for c in candidates {
+        let parent = c.path.parent().map(Path::to_path_buf).unwrap_or_default();
+        families
+            .entry((parent, c.name.family.to_lowercase()))
+            .or_default()
+            .push(c);
+    }
+
+    let open = files_open_for_writing();
+    let now = SystemTime::now();
+    let compress_after = Duration::from_secs(policy.compress_after_days * 86_400);
+    // The newest file of a family is always kept, whatever the policy says
+    let keep = policy.keep_per_family.max(1);
+    let mut planned = Vec::new();
+
+    for (_, mut members) in families {
+        members.sort_by_key(|c| Reverse(c.modified));
+
+        for (rank, c) in members.into_iter().enumerate() {
+            let age = now.duration_since(c.modified).unwrap_or_default();
+            let in_use = is_in_use(&c, &open, now);
+
+            let (action, savings) = if rank >= keep {
+                if in_use {
+                    (Action::InUse, 0)
+                } else {
+                    (Action::Delete, c.size)
+                }
+            } else if c.name.compressed || age < compress_after || c.size < MIN_COMPRESS_BYTES {
+                (Action::Keep, 0)
+            } else if in_use {
+                (Action::InUse, 0)
+            } else {
+                let savings = estimate_savings(&c.path, c.size, policy.compression);
+                if savings > 0 {
+                    (Action::Compress, savings)
+                } else {
+                    (Action::Keep, 0)
+                }
+            };
+
+            planned.push(PlannedFile {
+                path: c.path,
+                family: c.name.family,
+                size: c.size,
+                age_days: age.as_secs() / 86_400,
+                action,
+                savings,
+            });
+        }
+    }
+
+    planned.sort_by(|a, b| b.savings.cmp(&a.savings).then_with(|| a.path.cmp(&b.path)));
+    planned
+}
+
+// The archive must be no more readable than the log it replaces: auth.log is
+// root:adm 0640, and a umask-default copy would be world-readable. It starts
+// out owner-only and takes on the source's owner and mode before the rename.
+#[cfg(unix)]
+fn create_private(path: &Path) -> io::Result<File> {
+    use std::os::unix::fs::OpenOptionsExt;
+    fs::OpenOptions::new()
+        .write(true)
+        .create_new(true)
+        .mode(0o600)
+        .open(path)
+}
+
+#[cfg(not(unix))]
+fn create_private(path: &Path) -> io::Result<File> {
+    File::create(path)
+}
+
+#[cfg(unix)]
+fn copy_access(file: &File, source: &fs::Metadata) -> io::Result<()> {
+    use std::os::unix::fs::{fchown, MetadataExt};
+    fchown(file, Some(source.uid()), Some(source.gid()))?;
+    file.set_permissions(source.permissions())
+}
+
+#[cfg(not(unix))]
+fn copy_access(file: &File, source: &fs::Metadata) -> io::Result<()> {
+    file.set_permissions(source.permissions())
+}
+
+fn compress_file(path: &Path, codec: LogCompression) -> io::Result<u64> {
+    let meta = fs::metadata(path)?;
+
+    let mut target = path.as_os_str().to_os_string();
+    target.push(".");
+    target.push(codec.extension());
+    let target = PathBuf::from(target);
+    if target.exists() {
+        return Err(io::Error::new(
+            io::ErrorKind::AlreadyExists,
+            "compressed copy already exists",
+        ));
+    }
+
+    let mut tmp = target.as_os_str().to_os_string();
+    tmp.push(".tmp");
+    let tmp = PathBuf::from(tmp);
+
+    let _ = fs::remove_file(&tmp);
+    let written = File::open(path)
+        .and_then(|input| compress_stream(codec, input, create_private(&tmp)?))
+        .and_then(|out| {
+            copy_access(&out, &meta)?;
+            out.set_modified(meta.modified()?)?;
+            out.sync_all()?;
+            out.metadata().map(|m| m.len())
+        });
+    let compressed_len = match written {
+        Ok(len) => len,
+        Err(e) => {
+            let _ = fs::remove_file(&tmp);
+            return Err(e);
+        }
+    };
+
+    fs::rename(&tmp, &target)?;
+    if let Err(e) = fs::remove_file(path) {
+        let _ = fs::remove_file(&target);
+        return Err(e);
+    }
+
+    Ok(meta.len().saturating_sub(compressed_len))
+}
+
+struct Applied {
+    freed: u64,
+    errors: u32,
+    message: String,
+}
+
+fn apply_actions(planned: &[PlannedFile], action: Action, codec: LogCompression) -> Applied {
+    let mut freed = 0u64;
+    let mut done = 0u32;
+    let mut errors = 0u32;
+
+    for file in planned.iter().filter(|f| f.action == action) {
+        let result = match action {
+            Action::Compress => compress_file(&file.path, codec),
+            Action::Delete => fs::remove_file(&file.path).map(|_| file.size),
+            Action::Keep | Action::InUse => continue,
+        };
+        match result {
+            Ok(bytes) => {
+                freed += bytes;
+                done += 1;
+            }
+            Err(_) => errors += 1,
+        }
+    }
+
+    let verb = if action == Action::Compress {
+        "Compressed"
+    } else {
+        "Deleted"
+    };
+    let msg = if done == 0 && errors == 0 {
+        "Nothing to do".into()
+    } else if errors > 0 {
+        format!("{verb} {done} log files, {errors} skipped")
+    } else {
+        format!("{verb} {done} log files successfully")
+    };
+
+    Applied {
+        freed,
+        errors,
+        message: msg,
+    }
+}
+
+fn build_scan(planned: Vec<PlannedFile>) -> LogRetentionScan {
+    let mut scan = LogRetentionScan {
+        files: Vec::with_capacity(planned.len()),
+        compress_count: 0,
+        delete_count: 0,
+        in_use_count: 0,
+        compress_savings_bytes: 0,
+        delete_savings_bytes: 0,
+    };
+
+    for f in planned {
+        match f.action {
+            Action::Compress => {
+                scan.compress_count += 1;
+                scan.compress_savings_bytes += f.savings;
+            }
+            Action::Delete => {
+                scan.delete_count += 1;
+                scan.delete_savings_bytes += f.savings;
+            }
+            Action::InUse => scan.in_use_count += 1,
+            Action::Keep => {}
+        }
+        scan.files.push(LogFileAction {
+            path: f.path.to_string_lossy().into(),
+            family: f.family,
+            size_bytes: f.size,
+            age_days: f.age_days,
+            action: f.action.as_str().into(),
+            estimated_savings: f.savings,
+        });
+    }
+
+    scan
+}
+
+pub fn scan_log_items() -> Vec<CleanableItem> {
+    let mut items = Vec::new();
+    let policy = LogRetentionPolicy::default();
+    let scan = build_scan(plan_retention(&policy));
+
+    if scan.compress_savings_bytes > 1_000_000 {
+        items.push(CleanableItem {
+            id: "log_compress".into(),
+            name: "Compress Old Log Files".into(),
+            description: format!(
+                "{} log files older than {} days can be compressed with {} — nothing is deleted",
+                scan.compress_count,
+                policy.compress_after_days,
+                policy.compression.label(),
+            ),
+            size_bytes: scan.compress_savings_bytes,
+            path: "Various locations".into(),
+            risk: "safe".into(),
+            category: "System".into(),
+            requires_elevation: false,
+        });
+    }
+
+    if scan.delete_savings_bytes > 1_000_000 {
+        items.push(CleanableItem {
+            id: "log_prune".into(),
+            name: "Old Rotated Log Files".into(),
+            description: format!(
+                "{} rotated logs beyond the newest {} of each log",
+                scan.delete_count, policy.keep_per_family,
+            ),
+            size_bytes: scan.delete_savings_bytes,
+            path: "Various locations".into(),
+            risk: "moderate".into(),
+            category: "System".into(),
+            requires_elevation: false,
+        });
+    }
+
+    items
+}
+
+pub fn compress_old_logs() -> (u64, String) {
+    let policy = LogRetentionPolicy::default();
+    let applied = apply_actions(
+        &plan_retention(&policy),
+        Action::Compress,
+        policy.compression,
+    );
+    (applied.freed, applied.message)
+}
+
+pub fn prune_rotated_logs() -> (u64, String) {
+    let policy = LogRetentionPolicy::default();
+    let applied = apply_actions(&plan_retention(&policy), Action::Delete, policy.compression);
+    (applied.freed, applied.message)
+}
+
+#[tauri::command]
+pub fn scan_log_retention(policy: Option<LogRetentionPolicy>) -> LogRetentionScan {
+    build_scan(plan_retention(&policy.unwrap_or_default()))
+}
+
+#[tauri::command]
+pub fn apply_log_retention(policy: Option<LogRetentionPolicy>) -> CleanupSummary {
+    let policy = policy.unwrap_or_default();
+    let planned = plan_retention(&policy);
+    let mut results = Vec::new();
+    let mut total_freed = 0u64;
+
+    for (id, name, action) in [
+        ("log_compress", "Compress old log files", Action::Compress),
+        ("log_prune", "Delete old rotated logs", Action::Delete),
+    ] {
+        let applied = apply_actions(&planned, action, policy.compression);
+        total_freed += applied.freed;
+        results.push(CleanupResult {
+            id: id.into(),
+            name: name.into(),
+            success: applied.errors == 0 || applied.freed > 0,
+            bytes_freed: applied.freed,
+            message: applied.message,
+        });
+    }
+
+    CleanupSummary {
+        results,
+        total_freed,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_rotated_and_compressed_names() {
+        // (name, family, compressed, rotated, has_log_ext)
+        let cases = [
+            ("syslog", "syslog", false, false, false),
+            ("syslog.1", "syslog", false, true, false),
+            ("syslog.2.gz", "syslog", true, true, false),
+            ("app.log", "app.log", false, false, true),
+            ("app.log.3", "app.log", false, true, true),
+            ("app.log-20240131.gz", "app.log", true, true, true),
+            ("app.1.log", "app.log", false, true, true),
+            ("App.1.LOG", "App.LOG", false, true, true),
+            ("trace.etl.ZST", "trace.etl", true, false, true),
+            ("kern.log.4.xz", "kern.log", true, true, true),
+            ("messages.bz2", "messages", true, false, false),
+            ("dump.12345", "dump.12345", false, false, false),
+            ("app-2024.log", "app-2024.log", false, false, true),
+            (".1", ".1", false, false, false),
+        ];
+        for (name, family, compressed, rotated, has_log_ext) in cases {
+            let parsed = parse_log_name(name);
+            assert_eq!(
+                (
+                    parsed.family.as_str(),
+                    parsed.compressed,
+                    parsed.rotated,
+                    parsed.has_log_ext
+                ),
+                (family, compressed, rotated, has_log_ext),
+                "{name:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn counts_failed_actions() {
+        let dir = env::temp_dir().join(format!("logs-test-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let present = dir.join("app.log.2");
+        fs::write(&present, b"old entries").unwrap();
+        let planned: Vec<PlannedFile> = [present, dir.join("app.log.3")]
+            .into_iter()
+            .map(|path| PlannedFile {
+                path,
+                family: "app.log".into(),
+                size: 11,
+                age_days: 30,
+                action: Action::Delete,
+                savings: 11,
+            })
+            .collect();
+        let codec = LogRetentionPolicy::default().compression;
+
+        let applied = apply_actions(&planned, Action::Delete, codec);
+        assert_eq!((applied.freed, applied.errors), (11, 1));
+        assert_eq!(applied.message, "Deleted 1 log files, 1 skipped");
+
+        let applied = apply_actions(&planned, Action::Compress, codec);
+        assert_eq!((applied.freed, applied.errors), (0, 0));
+        assert_eq!(applied.message, "Nothing to do");
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -1,23 +1,324 @@
-use serde::Serialize;
-use sysinfo::{ProcessesToUpdate, System};
+use serde::{Deserialize, Serialize};
+use std::cmp::Reverse;
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use sysinfo::{
+    Pid, Process, ProcessRefreshKind, ProcessStatus, ProcessesToUpdate, System, UpdateKind,
+    Users, MINIMUM_CPU_UPDATE_INTERVAL,
+};
+use std::path::PathBuf;
 use std::process::Command;
+use std::sync::{Mutex, MutexGuard};
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, State};
 
-#[derive(Debug, Serialize)]
+use super::audit;
+use super::categories::{Categorizer, CategoryState, ProcessFacts};
+use super::network::SocketInfo;
+use super::pressure::{read_memory_pressure, MemoryPressure};
+use super::priority::{get_affinity, get_priority, PriorityLevel};
+use super::protection::{PolicyState, Protected, ProtectionPolicy, ProtectionRule, ROOT_PIDS};
+use super::sampler::{ProcessWatch, Sampler};
+use super::suspend::Suspended;
+
+#[cfg(target_os = "linux")]
+use super::network::connection_counts;
+#[cfg(not(windows))]
+use super::network::socket_table;
+#[cfg(unix)]
+use sysinfo::Signal;
+
+const DEFAULT_GRACE_PERIOD_MS: u64 = 3000;
+const KILL_POLL_INTERVAL: Duration = Duration::from_millis(100);
+const KILL_FORCE_TIMEOUT: Duration = Duration::from_secs(2);
+const MAX_OPEN_FILES: usize = 25;
+const STUCK_AFTER_SECS: u64 = 30;
+const RUNAWAY_AFTER_SECS: u64 = 120;
+
+#[derive(Debug, Serialize, Default)]
 pub struct MemoryInfo {
     pub total_bytes: u64,
-    pub used_bytes: u64,
-    pub free_bytes: u64,
+    pub used_bytes: u64,      // total minus available
+    pub free_bytes: u64,      // unused, not even as cache
+    pub available_bytes: u64, // free plus cache that can be dropped without swapping
     pub usage_percent: f64,
+    pub cached_bytes: u64,
+    pub buffers_bytes: u64,
+    pub shared_bytes: u64, // tmpfs and shared memory
+    pub swap_total_bytes: u64,
+    pub swap_used_bytes: u64,
+    pub committed_bytes: u64,
+    pub commit_limit_bytes: u64,
+    pub huge_pages_total: u64,
+    pub huge_pages_free: u64,
+    pub huge_page_size_bytes: u64,
+    pub pressure: Option<MemoryPressure>, // Linux PSI; None where unsupported
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, Clone)]
 pub struct ProcessInfo {
     pub pid: u32,
+    pub parent_pid: Option<u32>,
     pub name: String,
+    pub user: Option<String>,
+    pub start_time: u64,
     pub memory_bytes: u64,
     pub cpu_percent: f32,
+    pub disk_read_bytes_per_sec: u64,
+    pub disk_write_bytes_per_sec: u64,
+    pub connections: Option<u32>, // open TCP/UDP sockets; Linux, get_processes only
     pub is_zombie: bool,
+    pub is_suspended: bool,
+    pub priority: Option<PriorityLevel>,
+    pub affinity: Option<Vec<u32>>, // CPU indices the process may run on
+    pub pss_bytes: Option<u64>,     // accurate mode only
+    pub uss_bytes: Option<u64>,
+    pub swap_bytes: Option<u64>,
+    pub problem: Option<ProcessProblem>,
     pub category: String,
+    pub icon: Option<String>, // lucide icon hint from the matching rule
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum MemoryMetric {
+    #[default]
+    Rss,
+    Pss,
+    Uss,
+    Swap,
+}
+
+#[derive(Debug, Deserialize, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum ProcessSortKey {
+    #[default]
+    Memory,
+    Cpu,
+    Pid,
+    Name,
+    StartTime,
+    DiskIo,
+}
+
+#[derive(Debug, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum SortDirection {
+    Asc,
+    Desc,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+#[serde(default)]
+pub struct ProcessQuery {
+    pub filter: String, // case-insensitive match on name, user or category
+    pub sort_by: ProcessSortKey,
+    pub direction: Option<SortDirection>, // largest/newest first for numbers, A-Z for names
+    pub metric: MemoryMetric,             // what "memory" means when sorting by it
+    pub accurate: bool,
+    pub offset: usize,
+    pub limit: usize,
+}
+
+impl Default for ProcessQuery {
+    fn default() -> Self {
+        ProcessQuery {
+            filter: String::new(),
+            sort_by: ProcessSortKey::Memory,
+            direction: None,
+            metric: MemoryMetric::Rss,
+            accurate: false,
+            offset: 0,
+            limit: 50,
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct ProcessPage {
+    pub processes: Vec<ProcessInfo>,
+    pub total: usize, // matching processes before offset and limit
+    // Matching problem and suspended processes, whichever page they fall on
+    pub flagged: Vec<ProcessInfo>,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct ProcessProblem {
+    pub kind: String, // "zombie", "stuck", "runaway"
+    pub reason: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct OpenFile {
+    pub fd: u32,
+    pub path: String,
+    pub size_bytes: u64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ProcessDetails {
+    pub pid: u32,
+    pub parent_pid: Option<u32>,
+    pub name: String,
+    pub cmdline: Vec<String>,
+    pub exe: Option<String>,
+    pub cwd: Option<String>,
+    pub user: Option<String>,
+    pub start_time: u64,
+    pub thread_count: Option<u32>,
+    pub status: String,
+    pub environ_count: u32,
+    pub environ_bytes: u64,
+    pub open_fd_count: Option<u32>,
+    pub open_files: Vec<OpenFile>,
+    pub sockets: Vec<SocketInfo>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ProcessNode {
+    #[serde(flatten)]
+    pub process: ProcessInfo,
+    pub total_memory_bytes: u64,
+    pub total_cpu_percent: f32,
+    pub children: Vec<ProcessNode>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ProcessGroup {
+    pub key: String, // exe path of the application root, or its name if unreadable
+    pub name: String,
+    pub category: String,
+    pub icon: Option<String>,
+    pub total_memory_bytes: u64,
+    pub total_cpu_percent: f32,
+    pub process_count: u32,
+    pub pids: Vec<u32>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct KillOutcome {
+    pub pid: u32,
+    pub name: String,
+    pub success: bool,
+    pub stage: Option<KillStage>,
+    pub protected_by: Option<ProtectionRule>,
+    pub message: String,
+}
+
+#[derive(Debug, Serialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum KillStage {
+    Graceful,
+    Forced,
+}
+
+#[derive(Debug, Serialize)]
+pub struct KillResult {
+    pub pid: u32,
+    pub stage: KillStage,
+    pub message: String,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum KillError {
+    NotFound {
+        message: String,
+    },
+    Protected {
+        rule: ProtectionRule,
+        message: String,
+    },
+    Failed {
+        message: String,
+    },
+}
+
+impl KillError {
+    pub fn not_found() -> Self {
+        KillError::NotFound {
+            message: "Process not found — it may have already exited".into(),
+        }
+    }
+
+    pub fn message(&self) -> &str {
+        match self {
+            KillError::NotFound { message }
+            | KillError::Protected { message, .. }
+            | KillError::Failed { message } => message,
+        }
+    }
+}
+
+impl From<Protected> for KillError {
+    fn from(p: Protected) -> Self {
+        KillError::Protected {
+            rule: p.rule,
+            message: p.message,
+        }
+    }
+}
+
+impl fmt::Display for KillError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.message())
+    }
+}
+
+// For commands that read or configure rather than signal a process, so the
+// frontend never mistakes a failed listing or a bad limit for a refused kill.
+#[derive(Debug, Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum CommandError {
+    NotFound {
+        message: String,
+    },
+    Protected {
+        rule: ProtectionRule,
+        message: String,
+    },
+    Invalid {
+        message: String,
+    },
+    Failed {
+        message: String,
+    },
+    Unsupported {
+        message: String,
+    },
+}
+
+impl CommandError {
+    pub fn not_found() -> Self {
+        CommandError::NotFound {
+            message: "Process not found — it may have already exited".into(),
+        }
+    }
+
+    pub fn message(&self) -> &str {
+        match self {
+            CommandError::NotFound { message }
+            | CommandError::Protected { message, .. }
+            | CommandError::Invalid { message }
+            | CommandError::Failed { message }
+            | CommandError::Unsupported { message } => message,
+        }
+    }
+}
+
+impl From<Protected> for CommandError {
+    fn from(p: Protected) -> Self {
+        CommandError::Protected {
+            rule: p.rule,
+            message: p.message,
+        }
+    }
+}
+
+impl fmt::Display for CommandError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.message())
+    }
 }
 
 #[derive(Debug, Serialize)]
@@ -29,122 +330,1077 @@ pub struct VmInfo {
     pub vm_type: String,
 }
 
-fn categorize_process(name: &str) -> String {
-    let lower = name.to_lowercase();
-    if lower.contains("chrome")
-        || lower.contains("firefox")
-        || lower.contains("edge")
-        || lower.contains("opera")
-    {
-        "Browser".into()
-    } else if lower.contains("code")
-        || lower.contains("cursor")
-        || lower.contains("windsurf")
-        || lower.contains("node")
-        || lower.contains("python")
-        || lower.contains("rust")
-        || lower.contains("java")
-        || lower.contains("dotnet")
-        || lower.contains("postgres")
-    {
-        "Developer".into()
-    } else if lower.contains("discord")
-        || lower.contains("slack")
-        || lower.contains("teams")
-        || lower.contains("zoom")
-    {
-        "Communication".into()
-    } else if lower.contains("steam")
-        || lower.contains("epic")
-        || lower.contains("battle.net")
-        || lower.contains("game")
-    {
-        "Gaming".into()
-    } else if lower.contains("svchost")
-        || lower.contains("explorer")
-        || lower.contains("dwm")
-        || lower.contains("csrss")
-        || lower.contains("system")
-        || lower.contains("services")
-        || lower.contains("lsass")
-        || lower.contains("winlogon")
-    {
-        "System".into()
-    } else {
-        "Other".into()
+#[cfg(target_os = "linux")]
+fn read_meminfo() -> Option<MemoryInfo> {
+    let text = std::fs::read_to_string("/proc/meminfo").ok()?;
+    let fields: HashMap<&str, u64> = text
+        .lines()
+        .filter_map(|line| {
+            let (key, rest) = line.split_once(':')?;
+            let mut parts = rest.split_whitespace();
+            let value: u64 = parts.next()?.parse().ok()?;
+            // Sizes are in kB; page counts have no unit
+            let scale = if parts.next() == Some("kB") { 1024 } else { 1 };
+            Some((key, value * scale))
+        })
+        .collect();
+    let get = |key: &str| fields.get(key).copied().unwrap_or(0);
+
+    let total = *fields.get("MemTotal")?;
+    // MemAvailable only exists since Linux 3.14
+    let available = fields
+        .get("MemAvailable")
+        .copied()
+        .unwrap_or_else(|| get("MemFree") + get("Buffers") + get("Cached"));
+
+    Some(MemoryInfo {
+        total_bytes: total,
+        used_bytes: total.saturating_sub(available),
+        free_bytes: get("MemFree"),
+        available_bytes: available,
+        cached_bytes: get("Cached") + get("SReclaimable"),
+        buffers_bytes: get("Buffers"),
+        shared_bytes: get("Shmem"),
+        swap_total_bytes: get("SwapTotal"),
+        swap_used_bytes: get("SwapTotal").saturating_sub(get("SwapFree")),
+        committed_bytes: get("Committed_AS"),
+        commit_limit_bytes: get("CommitLimit"),
+        huge_pages_total: get("HugePages_Total"),
+        huge_pages_free: get("HugePages_Free"),
+        huge_page_size_bytes: get("Hugepagesize"),
+        ..Default::default()
+    })
+}
+
+// Windows reports cache and commit charge in pages.
+#[cfg(windows)]
+fn with_performance_info(mut info: MemoryInfo) -> MemoryInfo {
+    use windows::Win32::System::ProcessStatus::{GetPerformanceInfo, PERFORMANCE_INFORMATION};
+
+    let mut perf = PERFORMANCE_INFORMATION::default();
+    let size = std::mem::size_of::<PERFORMANCE_INFORMATION>() as u32;
+    if unsafe { GetPerformanceInfo(&mut perf, size) }.is_ok() {
+        let page = perf.PageSize as u64;
+        info.cached_bytes = perf.SystemCache as u64 * page;
+        info.committed_bytes = perf.CommitTotal as u64 * page;
+        info.commit_limit_bytes = perf.CommitLimit as u64 * page;
     }
+    info
 }
 
-#[tauri::command]
-pub fn get_memory_info() -> MemoryInfo {
+fn sysinfo_memory() -> MemoryInfo {
     let mut sys = System::new();
     sys.refresh_memory();
 
     let total = sys.total_memory();
-    let used = sys.used_memory();
-    let free = total.saturating_sub(used);
-    let usage_percent = if total > 0 {
-        (used as f64 / total as f64) * 100.0
+    let available = sys.available_memory();
+    let info = MemoryInfo {
+        total_bytes: total,
+        used_bytes: total.saturating_sub(available),
+        free_bytes: sys.free_memory(),
+        available_bytes: available,
+        swap_total_bytes: sys.total_swap(),
+        swap_used_bytes: sys.used_swap(),
+        ..Default::default()
+    };
+    #[cfg(windows)]
+    let info = with_performance_info(info);
+    info
+}
+
+#[tauri::command]
+pub fn get_memory_info() -> MemoryInfo {
+    #[cfg(target_os = "linux")]
+    let info = read_meminfo();
+    #[cfg(not(target_os = "linux"))]
+    let info = None;
+
+    let mut info = info.unwrap_or_else(sysinfo_memory);
+    info.usage_percent = if info.total_bytes > 0 {
+        (info.used_bytes as f64 / info.total_bytes as f64) * 100.0
     } else {
         0.0
     };
+    info.pressure = read_memory_pressure();
+    info
+}
 
-    MemoryInfo {
-        total_bytes: total,
-        used_bytes: used,
-        free_bytes: free,
-        usage_percent,
+// The default refresh skips owners and command lines, which the protection
+// policy and category rules need.
+pub fn refresh_all(sys: &mut System) {
+    sys.refresh_processes_specifics(
+        ProcessesToUpdate::All,
+        true,
+        ProcessRefreshKind::nothing()
+            .with_memory()
+            .with_cpu()
+            .with_disk_usage()
+            .with_exe(UpdateKind::OnlyIfNotSet)
+            .with_cmd(UpdateKind::OnlyIfNotSet)
+            .with_user(UpdateKind::OnlyIfNotSet),
+    );
+}
+
+// One long-lived System behind every process query. sysinfo keeps each
+// process between refreshes, so a refresh only rereads what can change and
+// CPU usage is measured since the previous call rather than over a sleep.
+// Kills keep their own System so a grace period doesn't block queries.
+pub struct SharedSystem {
+    sys: Mutex<System>,
+    // When the last refresh ran, and how long before it the previous one did
+    last_refresh: Mutex<(Instant, Duration)>,
+}
+
+impl Default for SharedSystem {
+    fn default() -> Self {
+        let mut sys = System::new();
+        refresh_all(&mut sys);
+        SharedSystem {
+            sys: Mutex::new(sys),
+            last_refresh: Mutex::new((Instant::now(), Duration::ZERO)),
+        }
     }
 }
 
-#[tauri::command]
-pub fn get_processes() -> Vec<ProcessInfo> {
-    let mut sys = System::new();
-    sys.refresh_processes(ProcessesToUpdate::All, true);
-    std::thread::sleep(std::time::Duration::from_millis(500));
-    sys.refresh_processes(ProcessesToUpdate::All, true);
+impl SharedSystem {
+    // Calls closer together than sysinfo's minimum CPU interval would read
+    // 0% CPU, so they share the previous refresh instead.
+    pub fn refreshed(&self) -> MutexGuard<'_, System> {
+        let mut sys = self.sys.lock().unwrap();
+        let mut last_refresh = self.last_refresh.lock().unwrap();
+        let elapsed = last_refresh.0.elapsed();
+        if elapsed >= MINIMUM_CPU_UPDATE_INTERVAL {
+            refresh_all(&mut sys);
+            *last_refresh = (Instant::now(), elapsed);
+        }
+        sys
+    }
+
+    // The span the disk usage counters of the last refresh cover. Zero until
+    // the second refresh, as the first one counts from process start.
+    pub fn interval(&self) -> Duration {
+        self.last_refresh.lock().unwrap().1
+    }
+}
+
+fn parent_pid(sys: &System, p: &Process) -> Option<u32> {
+    let parent = sys.process(p.parent()?)?;
+    // Windows doesn't reparent orphans, so the parent pid may have been reused
+    // by a process that started after this one.
+    (parent.pid() != p.pid() && parent.start_time() <= p.start_time())
+        .then(|| parent.pid().as_u32())
+}
+
+fn detect_problem(
+    sys: &System,
+    p: &Process,
+    watch: Option<&ProcessWatch>,
+) -> Option<ProcessProblem> {
+    let problem = |kind: &str, reason: String| {
+        Some(ProcessProblem {
+            kind: kind.into(),
+            reason,
+        })
+    };
+
+    if p.status() == ProcessStatus::Zombie {
+        let parent = p.parent().and_then(|pid| sys.process(pid));
+        return problem(
+            "zombie",
+            match parent {
+                Some(parent) => format!(
+                    "Exited, but its parent {} (PID {}) hasn't collected it — ending the parent clears it",
+                    parent.name().to_string_lossy(),
+                    parent.pid()
+                ),
+                None => "Exited, but its parent hasn't collected it".into(),
+            },
+        );
+    }
 
-    let mut processes: Vec<ProcessInfo> = sys
-        .processes()
+    let watch = watch?;
+    let (pid, start_time) = (p.pid().as_u32(), p.start_time());
+    if let Some(secs) = watch
+        .stuck_secs(pid, start_time)
+        .filter(|&s| s >= STUCK_AFTER_SECS)
+    {
+        return problem(
+            "stuck",
+            format!(
+                "Waiting on I/O it can't be interrupted from for {secs}s — usually a hung disk or network mount"
+            ),
+        );
+    }
+    if let Some((secs, cpu)) = watch
+        .busy_secs(pid, start_time)
+        .filter(|&(s, _)| s >= RUNAWAY_AFTER_SECS)
+    {
+        return problem(
+            "runaway",
+            format!("Has used {cpu:.0}% CPU without a break for {secs}s"),
+        );
+    }
+    None
+}
+
+fn list_processes(
+    sys: &System,
+    watch: Option<&ProcessWatch>,
+    categorizer: &Categorizer,
+) -> Vec<ProcessInfo> {
+    let users = Users::new_with_refreshed_list();
+    sys.processes()
         .values()
+        // Linux lists every thread as a task sharing its process's memory
+        .filter(|p| p.thread_kind().is_none())
         .map(|p| {
-            let name = p.name().to_string_lossy().to_string();
-            let mem = p.memory();
-            let cpu = p.cpu_usage();
-
-            let is_zombie = cpu > 25.0 && mem < 10_000_000;
+            let facts = ProcessFacts::of(sys, p);
+            let category = categorizer.categorize(&facts);
 
             ProcessInfo {
                 pid: p.pid().as_u32(),
-                name: name.clone(),
-                memory_bytes: mem,
-                cpu_percent: cpu,
-                is_zombie,
-                category: categorize_process(&name),
+                parent_pid: parent_pid(sys, p),
+                name: facts.name,
+                user: p
+                    .user_id()
+                    .and_then(|uid| users.get_user_by_id(uid))
+                    .map(|u| u.name().to_string()),
+                start_time: p.start_time(),
+                memory_bytes: p.memory(),
+                cpu_percent: p.cpu_usage(),
+                disk_read_bytes_per_sec: 0,
+                disk_write_bytes_per_sec: 0,
+                connections: None,
+                is_zombie: p.status() == ProcessStatus::Zombie,
+                is_suspended: p.status() == ProcessStatus::Stop,
+                priority: get_priority(p.pid().as_u32()),
+                affinity: get_affinity(p.pid().as_u32()),
+                pss_bytes: None,
+                uss_bytes: None,
+                swap_bytes: None,
+                problem: detect_problem(sys, p, watch),
+                category: category.name,
+                icon: category.icon,
+            }
+        })
+        .collect()
+}
+
+type ChildMap = HashMap<Option<u32>, Vec<ProcessInfo>>;
+
+fn children_by_parent(processes: Vec<ProcessInfo>) -> ChildMap {
+    let mut children = ChildMap::new();
+    for p in processes {
+        children.entry(p.parent_pid).or_default().push(p);
+    }
+    children
+}
+
+fn build_node(process: ProcessInfo, children: &mut ChildMap) -> ProcessNode {
+    let mut nodes: Vec<ProcessNode> = children
+        .remove(&Some(process.pid))
+        .unwrap_or_default()
+        .into_iter()
+        .map(|c| build_node(c, children))
+        .collect();
+    nodes.sort_by_key(|n| Reverse(n.total_memory_bytes));
+
+    ProcessNode {
+        total_memory_bytes: process.memory_bytes
+            + nodes.iter().map(|n| n.total_memory_bytes).sum::<u64>(),
+        total_cpu_percent: process.cpu_percent
+            + nodes.iter().map(|n| n.total_cpu_percent).sum::<f32>(),
+        process,
+        children: nodes,
+    }
+}
+
+// Descendants of `pid` deepest first, so no child outlives its parent.
+fn collect_descendants(pid: u32, children: &ChildMap, out: &mut Vec<ProcessInfo>) {
+    for child in children.get(&Some(pid)).into_iter().flatten() {
+        collect_descendants(child.pid, children, out);
+        out.push(child.clone());
+    }
+}
+
+struct Member {
+    info: ProcessInfo,
+    key: String,
+    exe: Option<PathBuf>,
+}
+
+// A child belongs to its parent's application if it runs the same binary or
+// one installed under the parent's directory. Directories on PATH are shared
+// by unrelated tools, so they don't count.
+fn same_app(child: &Member, parent: &Member, shared_dirs: &HashSet<PathBuf>) -> bool {
+    if child.key == parent.key {
+        return true;
+    }
+    match (&child.exe, parent.exe.as_ref().and_then(|e| e.parent())) {
+        (Some(exe), Some(dir)) => !shared_dirs.contains(dir) && exe.starts_with(dir),
+        _ => false,
+    }
+}
+
+// Groups are keyed by their application root: the highest ancestor reachable
+// through `same_app` links. Separate instances of one app share a key. Each
+// member comes with its depth below the root.
+fn group_processes(
+    sys: &System,
+    watch: Option<&ProcessWatch>,
+    categorizer: &Categorizer,
+) -> HashMap<String, Vec<(ProcessInfo, usize)>> {
+    let shared_dirs: HashSet<PathBuf> = std::env::var_os("PATH")
+        .map(|path| std::env::split_paths(&path).collect())
+        .unwrap_or_default();
+    let members: HashMap<u32, Member> = list_processes(sys, watch, categorizer)
+        .into_iter()
+        .map(|info| {
+            let exe = sys
+                .process(Pid::from_u32(info.pid))
+                .and_then(|p| p.exe())
+                .map(|e| e.to_path_buf());
+            let key = match &exe {
+                Some(e) => e.to_string_lossy().to_string(),
+                None => info.name.clone(),
+            };
+            (info.pid, Member { info, key, exe })
+        })
+        .collect();
+
+    let mut groups: HashMap<String, Vec<(ProcessInfo, usize)>> = HashMap::new();
+    for member in members.values() {
+        let (mut root, mut depth) = (member, 0);
+        while let Some(parent) = root.info.parent_pid.and_then(|pid| members.get(&pid)) {
+            if ROOT_PIDS.contains(&parent.info.pid)
+                || depth >= 64
+                || !same_app(root, parent, &shared_dirs)
+            {
+                break;
+            }
+            root = parent;
+            depth += 1;
+        }
+        groups
+            .entry(root.key.clone())
+            .or_default()
+            .push((member.info.clone(), depth));
+    }
+    groups
+}
+
+// PSS splits each shared page between the processes mapping it, so PSS adds
+// up across processes; USS is memory only this process maps.
+#[cfg(target_os = "linux")]
+fn parse_smaps_rollup(text: &str) -> Option<(u64, u64, u64)> {
+    let field = |key: &str| -> Option<u64> {
+        text.lines()
+            .find_map(|line| line.strip_prefix(key)?.strip_prefix(':'))
+            .and_then(|rest| rest.split_whitespace().next()?.parse::<u64>().ok())
+            .map(|kb| kb * 1024)
+    };
+    // Kernel threads have an empty rollup
+    let pss = field("Pss")?;
+    Some((
+        pss,
+        field("Private_Clean").unwrap_or(0) + field("Private_Dirty").unwrap_or(0),
+        field("Swap").unwrap_or(0),
+    ))
+}
+
+#[cfg(target_os = "linux")]
+fn read_smaps_rollup(pid: u32) -> Option<(u64, u64, u64)> {
+    parse_smaps_rollup(&std::fs::read_to_string(format!("/proc/{pid}/smaps_rollup")).ok()?)
+}
+
+#[cfg(not(target_os = "linux"))]
+fn read_smaps_rollup(_pid: u32) -> Option<(u64, u64, u64)> {
+    None
+}
+
+fn read_accurate_memory(processes: &mut [ProcessInfo]) {
+    for p in processes.iter_mut() {
+        if let Some((pss, uss, swap)) = read_smaps_rollup(p.pid) {
+            p.pss_bytes = Some(pss);
+            p.uss_bytes = Some(uss);
+            p.swap_bytes = Some(swap);
+        }
+    }
+}
+
+// sysinfo reports the bytes read and written since the previous refresh.
+// Windows counts all I/O there, network and device traffic included.
+fn add_disk_rates(sys: &System, processes: &mut [ProcessInfo], interval: Duration) {
+    let secs = interval.as_secs_f64();
+    if secs <= 0.0 {
+        return;
+    }
+    for p in processes.iter_mut() {
+        if let Some(process) = sys.process(Pid::from_u32(p.pid)) {
+            let usage = process.disk_usage();
+            p.disk_read_bytes_per_sec = (usage.read_bytes as f64 / secs) as u64;
+            p.disk_write_bytes_per_sec = (usage.written_bytes as f64 / secs) as u64;
+        }
+    }
+}
+
+#[cfg(target_os = "linux")]
+fn add_connection_counts(processes: &mut [ProcessInfo]) {
+    let pids: Vec<u32> = processes.iter().map(|p| p.pid).collect();
+    let counts = connection_counts(&pids);
+    for p in processes.iter_mut() {
+        p.connections = counts.get(&p.pid).copied();
+    }
+}
+
+#[cfg(not(target_os = "linux"))]
+fn add_connection_counts(_processes: &mut [ProcessInfo]) {}
+
+impl ProcessInfo {
+    // Processes whose smaps can't be read fall back to RSS, an upper bound.
+    fn metric(&self, metric: MemoryMetric) -> u64 {
+        match metric {
+            MemoryMetric::Rss => self.memory_bytes,
+            MemoryMetric::Pss => self.pss_bytes.unwrap_or(self.memory_bytes),
+            MemoryMetric::Uss => self.uss_bytes.unwrap_or(self.memory_bytes),
+            MemoryMetric::Swap => self.swap_bytes.unwrap_or(0),
+        }
+    }
+}
+
+// `filter` is already lowercased
+fn matches_filter(p: &ProcessInfo, filter: &str) -> bool {
+    filter.is_empty()
+        || p.name.to_lowercase().contains(filter)
+        || p.category.to_lowercase().contains(filter)
+        || p.user
+            .as_ref()
+            .is_some_and(|u| u.to_lowercase().contains(filter))
+}
+
+impl ProcessQuery {
+    fn descending(&self) -> bool {
+        match self.direction {
+            Some(direction) => direction == SortDirection::Desc,
+            None => !matches!(self.sort_by, ProcessSortKey::Pid | ProcessSortKey::Name),
+        }
+    }
+
+    fn sort(&self, processes: &mut [ProcessInfo]) {
+        // Ties keep PID order, so pages don't shuffle between calls
+        processes.sort_by_key(|p| p.pid);
+        match self.sort_by {
+            ProcessSortKey::Memory => processes.sort_by_key(|p| p.metric(self.metric)),
+            ProcessSortKey::Cpu => {
+                processes.sort_by(|a, b| a.cpu_percent.total_cmp(&b.cpu_percent))
+            }
+            ProcessSortKey::Pid => {}
+            ProcessSortKey::Name => processes.sort_by_cached_key(|p| p.name.to_lowercase()),
+            ProcessSortKey::StartTime => processes.sort_by_key(|p| p.start_time),
+            ProcessSortKey::DiskIo => {
+                processes.sort_by_key(|p| p.disk_read_bytes_per_sec + p.disk_write_bytes_per_sec)
+            }
+        }
+        if self.descending() {
+            processes.reverse();
+        }
+    }
+}
+
+#[tauri::command]
+pub fn get_processes(
+    sampler: State<'_, Sampler>,
+    categories: State<'_, CategoryState>,
+    suspended: State<'_, Suspended>,
+    system: State<'_, SharedSystem>,
+    query: Option<ProcessQuery>,
+) -> ProcessPage {
+    let query = query.unwrap_or_default();
+    let sys = system.refreshed();
+    let watch = sampler.watch.lock().unwrap();
+    let categorizer = categories.lock().unwrap();
+    let mut processes = list_processes(&sys, Some(&watch), &categorizer);
+    suspended.mark(&sys, &mut processes);
+    add_disk_rates(&sys, &mut processes, system.interval());
+
+    let filter = query.filter.trim().to_lowercase();
+    processes.retain(|p| matches_filter(p, &filter));
+    let sorts_by_smaps =
+        query.sort_by == ProcessSortKey::Memory && query.metric != MemoryMetric::Rss;
+    if query.accurate || sorts_by_smaps {
+        read_accurate_memory(&mut processes);
+    }
+    query.sort(&mut processes);
+
+    let mut flagged: Vec<ProcessInfo> = processes
+        .iter()
+        .filter(|p| p.problem.is_some() || p.is_suspended)
+        .cloned()
+        .collect();
+    let total = processes.len();
+    let mut processes: Vec<ProcessInfo> = processes
+        .into_iter()
+        .skip(query.offset)
+        .take(query.limit)
+        .collect();
+    drop(sys);
+    // Walking every fd is too slow for the whole list, so only for what's returned
+    add_connection_counts(&mut processes);
+    add_connection_counts(&mut flagged);
+
+    ProcessPage {
+        processes,
+        total,
+        flagged,
+    }
+}
+
+// /proc/<pid>/fd/<n> links to a path, or to socket:[inode], pipe:[inode],
+// anon_inode:[eventfd] and so on.
+#[cfg(not(windows))]
+fn read_fds(pid: u32) -> Option<(u32, Vec<OpenFile>, Vec<u64>)> {
+    let entries = std::fs::read_dir(format!("/proc/{pid}/fd")).ok()?;
+    let mut count = 0u32;
+    let mut files = Vec::new();
+    let mut socket_inodes = Vec::new();
+
+    for entry in entries.flatten() {
+        count += 1;
+        let target = match std::fs::read_link(entry.path()) {
+            Ok(t) => t.to_string_lossy().to_string(),
+            Err(_) => continue,
+        };
+        if let Some(inode) = target
+            .strip_prefix("socket:[")
+            .and_then(|t| t.strip_suffix(']'))
+        {
+            if let Ok(inode) = inode.parse() {
+                socket_inodes.push(inode);
+            }
+        } else if target.starts_with('/') {
+            files.push(OpenFile {
+                fd: entry.file_name().to_string_lossy().parse().unwrap_or(0),
+                size_bytes: std::fs::metadata(&target).map(|m| m.len()).unwrap_or(0),
+                path: target,
+            });
+        }
+    }
+
+    files.sort_by_key(|f| Reverse(f.size_bytes));
+    files.truncate(MAX_OPEN_FILES);
+    Some((count, files, socket_inodes))
+}
+
+#[cfg(windows)]
+fn handle_count(pid: u32) -> Option<u32> {
+    use windows::Win32::Foundation::CloseHandle;
+    use windows::Win32::System::Threading::{
+        GetProcessHandleCount, OpenProcess, PROCESS_QUERY_LIMITED_INFORMATION,
+    };
+
+    unsafe {
+        let handle = OpenProcess(PROCESS_QUERY_LIMITED_INFORMATION, false, pid).ok()?;
+        let mut count = 0u32;
+        let ok = GetProcessHandleCount(handle, &mut count).is_ok();
+        let _ = CloseHandle(handle);
+        ok.then_some(count)
+    }
+}
+
+#[cfg(windows)]
+fn thread_count(pid: u32) -> Option<u32> {
+    use windows::Win32::Foundation::CloseHandle;
+    use windows::Win32::System::Diagnostics::ToolHelp::{
+        CreateToolhelp32Snapshot, Thread32First, Thread32Next, TH32CS_SNAPTHREAD, THREADENTRY32,
+    };
+
+    unsafe {
+        let snapshot = CreateToolhelp32Snapshot(TH32CS_SNAPTHREAD, 0).ok()?;
+        let mut entry = THREADENTRY32 {
+            dwSize: std::mem::size_of::<THREADENTRY32>() as u32,
+            ..Default::default()
+        };
+        let mut count = 0u32;
+        let mut more = Thread32First(snapshot, &mut entry).is_ok();
+        while more {
+            if entry.th32OwnerProcessID == pid {
+                count += 1;
+            }
+            more = Thread32Next(snapshot, &mut entry).is_ok();
+        }
+        let _ = CloseHandle(snapshot);
+        Some(count)
+    }
+}
+
+#[tauri::command]
+pub fn get_process_groups(
+    sampler: State<'_, Sampler>,
+    categories: State<'_, CategoryState>,
+    system: State<'_, SharedSystem>,
+) -> Vec<ProcessGroup> {
+    let sys = system.refreshed();
+    let watch = sampler.watch.lock().unwrap();
+    let categorizer = categories.lock().unwrap();
+
+    let mut groups: Vec<ProcessGroup> = group_processes(&sys, Some(&watch), &categorizer)
+        .into_iter()
+        .map(|(key, mut members)| {
+            members.sort_by_key(|(p, _)| Reverse(p.memory_bytes));
+            // Named after its largest application root
+            let (lead, _) = members
+                .iter()
+                .find(|(_, depth)| *depth == 0)
+                .unwrap_or(&members[0]);
+            ProcessGroup {
+                key,
+                name: lead.name.clone(),
+                category: lead.category.clone(),
+                icon: lead.icon.clone(),
+                total_memory_bytes: members.iter().map(|(p, _)| p.memory_bytes).sum(),
+                total_cpu_percent: members.iter().map(|(p, _)| p.cpu_percent).sum(),
+                process_count: members.len() as u32,
+                pids: members.iter().map(|(p, _)| p.pid).collect(),
             }
         })
-        .filter(|p| p.memory_bytes > 1_000_000)
         .collect();
+    groups.sort_by_key(|g| Reverse(g.total_memory_bytes));
+    groups
+}
+
+#[tauri::command]
+pub fn get_process_details(
+    pid: u32,
+    system: State<'_, SharedSystem>,
+) -> Result<ProcessDetails, String> {
+    let mut sys = system.refreshed();
+    // CPU and disk are left alone so the next listing still measures from the
+    // last one.
+    sys.refresh_processes_specifics(
+        ProcessesToUpdate::Some(&[Pid::from_u32(pid)]),
+        true,
+        ProcessRefreshKind::everything()
+            .without_cpu()
+            .without_disk_usage()
+            .with_cwd(UpdateKind::Always)
+            .with_environ(UpdateKind::Always),
+    );
+    let process = sys
+        .process(Pid::from_u32(pid))
+        .ok_or("Process not found — it may have already exited")?;
+
+    // Everything else reads files, so it happens after the lock is released
+    let uid = process.user_id().cloned();
+    let mut details = ProcessDetails {
+        pid,
+        parent_pid: parent_pid(&sys, process),
+        name: process.name().to_string_lossy().to_string(),
+        cmdline: process
+            .cmd()
+            .iter()
+            .map(|a| a.to_string_lossy().to_string())
+            .collect(),
+        exe: process.exe().map(|p| p.to_string_lossy().to_string()),
+        cwd: process.cwd().map(|p| p.to_string_lossy().to_string()),
+        user: None,
+        start_time: process.start_time(),
+        thread_count: process.tasks().map(|t| t.len() as u32),
+        status: process.status().to_string(),
+        environ_count: process.environ().len() as u32,
+        environ_bytes: process.environ().iter().map(|e| e.len() as u64 + 1).sum(),
+        open_fd_count: None,
+        open_files: Vec::new(),
+        sockets: Vec::new(),
+    };
+    drop(sys);
+
+    details.user = uid.and_then(|uid| {
+        Users::new_with_refreshed_list()
+            .get_user_by_id(&uid)
+            .map(|u| u.name().to_string())
+    });
+
+    #[cfg(windows)]
+    {
+        details.open_fd_count = handle_count(pid);
+        details.thread_count = thread_count(pid);
+    }
 
-    processes.sort_by(|a, b| b.memory_bytes.cmp(&a.memory_bytes));
-    processes.truncate(50);
-    processes
+    #[cfg(not(windows))]
+    if let Some((count, files, inodes)) = read_fds(pid) {
+        let table = socket_table();
+        details.open_fd_count = Some(count);
+        details.open_files = files;
+        details.sockets = inodes
+            .iter()
+            .filter_map(|inode| table.get(inode).cloned())
+            .collect();
+    }
+
+    Ok(details)
 }
 
 #[tauri::command]
-pub fn kill_process(pid: u32) -> Result<String, String> {
-    let s = System::new();
-    let pid = sysinfo::Pid::from_u32(pid);
+pub fn get_process_tree(
+    sampler: State<'_, Sampler>,
+    categories: State<'_, CategoryState>,
+    suspended: State<'_, Suspended>,
+    system: State<'_, SharedSystem>,
+) -> Vec<ProcessNode> {
+    let sys = system.refreshed();
+    let watch = sampler.watch.lock().unwrap();
+    let categorizer = categories.lock().unwrap();
+    let mut processes = list_processes(&sys, Some(&watch), &categorizer);
+    suspended.mark(&sys, &mut processes);
+    add_disk_rates(&sys, &mut processes, system.interval());
+    let mut children = children_by_parent(processes);
+
+    let mut roots: Vec<ProcessNode> = children
+        .remove(&None)
+        .unwrap_or_default()
+        .into_iter()
+        .map(|p| build_node(p, &mut children))
+        .collect();
+    roots.sort_by_key(|n| Reverse(n.total_memory_bytes));
+    roots
+}
 
-    if let Some(process) = s.process(pid) {
-        if process.kill() {
-            Ok("Process terminated".into())
-        } else {
-            Err("Failed to terminate process — it may require admin rights".into())
+struct Target {
+    pid: Pid,
+    start_time: u64,
+    asked: bool,
+    stage: Option<KillStage>,
+}
+
+// A pid is only the same process if its start time matches; exited children
+// linger as zombies until their parent reaps them.
+fn is_running(sys: &System, t: &Target) -> bool {
+    sys.process(t.pid)
+        .is_some_and(|p| p.start_time() == t.start_time && p.status() != ProcessStatus::Zombie)
+}
+
+fn wait_for_exit(
+    sys: &mut System,
+    targets: &mut [Option<Target>],
+    timeout: Duration,
+    stage: KillStage,
+) {
+    let deadline = Instant::now() + timeout;
+    loop {
+        let pids: Vec<Pid> = targets
+            .iter()
+            .flatten()
+            .filter(|t| t.stage.is_none())
+            .map(|t| t.pid)
+            .collect();
+        if pids.is_empty() {
+            return;
+        }
+        sys.refresh_processes(ProcessesToUpdate::Some(&pids), true);
+        for t in targets.iter_mut().flatten().filter(|t| t.stage.is_none()) {
+            if !is_running(sys, t) {
+                t.stage = Some(stage);
+            }
+        }
+        if Instant::now() >= deadline {
+            return;
+        }
+        std::thread::sleep(KILL_POLL_INTERVAL);
+    }
+}
+
+#[cfg(unix)]
+fn request_close(process: &Process) -> bool {
+    let asked = process.kill_with(Signal::Term).unwrap_or(false);
+    // A stopped process can't act on SIGTERM until it is continued.
+    if asked && process.status() == ProcessStatus::Stop {
+        let _ = process.kill_with(Signal::Continue);
+    }
+    asked
+}
+
+// Windows has no SIGTERM; the closest thing is asking each top-level window to
+// close, which lets the app save state or prompt. Console and background
+// processes have no windows and go straight to a forced kill.
+#[cfg(windows)]
+fn request_close(process: &Process) -> bool {
+    use windows::Win32::Foundation::{BOOL, HWND, LPARAM, WPARAM};
+    use windows::Win32::UI::WindowsAndMessaging::{
+        EnumWindows, GetWindowThreadProcessId, IsWindowVisible, PostMessageW, WM_CLOSE,
+    };
+
+    struct Search {
+        pid: u32,
+        posted: bool,
+    }
+
+    unsafe extern "system" fn visit(hwnd: HWND, lparam: LPARAM) -> BOOL {
+        let search = &mut *(lparam.0 as *mut Search);
+        let mut owner = 0u32;
+        GetWindowThreadProcessId(hwnd, Some(&mut owner as *mut u32));
+        if owner == search.pid
+            && IsWindowVisible(hwnd).as_bool()
+            && PostMessageW(hwnd, WM_CLOSE, WPARAM(0), LPARAM(0)).is_ok()
+        {
+            search.posted = true;
+        }
+        BOOL(1)
+    }
+
+    let mut search = Search {
+        pid: process.pid().as_u32(),
+        posted: false,
+    };
+    unsafe {
+        let _ = EnumWindows(Some(visit), LPARAM(&mut search as *mut Search as isize));
+    }
+    search.posted
+}
+
+#[cfg(not(any(unix, windows)))]
+fn request_close(_process: &Process) -> bool {
+    false
+}
+
+// Asks every target to close, waits up to `grace` for all of them, then
+// force-kills whatever is left. Pids that are already gone come back as None.
+fn terminate(sys: &mut System, pids: &[u32], grace: Duration) -> Vec<Option<Target>> {
+    let mut targets: Vec<Option<Target>> = pids
+        .iter()
+        .map(|&pid| {
+            let pid = Pid::from_u32(pid);
+            sys.process(pid).map(|process| Target {
+                pid,
+                start_time: process.start_time(),
+                asked: request_close(process),
+                stage: None,
+            })
+        })
+        .collect();
+
+    if targets.iter().flatten().any(|t| t.asked) {
+        wait_for_exit(sys, &mut targets, grace, KillStage::Graceful);
+    }
+
+    let mut forced = false;
+    for t in targets.iter().flatten().filter(|t| t.stage.is_none()) {
+        match sys.process(t.pid) {
+            Some(process) if is_running(sys, t) => forced |= process.kill(),
+            _ => {}
+        }
+    }
+    if forced {
+        wait_for_exit(sys, &mut targets, KILL_FORCE_TIMEOUT, KillStage::Forced);
+    }
+
+    targets
+}
+
+fn kill_message(t: &Target, grace: Duration) -> Result<String, String> {
+    match t.stage {
+        Some(KillStage::Graceful) => Ok("Process closed gracefully".into()),
+        Some(KillStage::Forced) if t.asked => Ok(format!(
+            "Process force-killed after ignoring the close request for {:.1}s",
+            grace.as_secs_f32()
+        )),
+        Some(KillStage::Forced) => Ok("Process force-killed".into()),
+        None => Err("Failed to terminate process — it may require admin rights".into()),
+    }
+}
+
+fn grace_period(grace_period_ms: Option<u64>) -> Duration {
+    Duration::from_millis(grace_period_ms.unwrap_or(DEFAULT_GRACE_PERIOD_MS))
+}
+
+// Protected targets are left running and reported; the rest are terminated
+// together in the given order.
+fn kill_targets(
+    sys: &mut System,
+    policy: &ProtectionPolicy,
+    targets: Vec<ProcessInfo>,
+    grace: Duration,
+) -> Vec<KillOutcome> {
+    let mut outcomes = Vec::new();
+    let mut allowed = Vec::new();
+    for p in targets {
+        match policy.check_pid(sys, p.pid) {
+            Ok(()) => allowed.push(p),
+            Err(protected) => outcomes.push(KillOutcome {
+                pid: p.pid,
+                name: p.name,
+                success: false,
+                stage: None,
+                protected_by: Some(protected.rule),
+                message: protected.message,
+            }),
+        }
+    }
+
+    let pids: Vec<u32> = allowed.iter().map(|p| p.pid).collect();
+    let results = terminate(sys, &pids, grace);
+
+    outcomes.extend(allowed.into_iter().zip(results).map(|(p, target)| {
+        let (success, stage, message) = match target {
+            Some(t) => match kill_message(&t, grace) {
+                Ok(message) => (true, t.stage, message),
+                Err(message) => (false, None, message),
+            },
+            None => (true, None, "Process already exited".into()),
+        };
+        KillOutcome {
+            pid: p.pid,
+            name: p.name,
+            success,
+            stage,
+            protected_by: None,
+            message,
+        }
+    }));
+    outcomes
+}
+
+fn kill_one(pid: u32, grace: Duration, policy: &ProtectionPolicy) -> Result<KillResult, KillError> {
+    let mut sys = System::new();
+    refresh_all(&mut sys);
+
+    if sys.process(Pid::from_u32(pid)).is_none() {
+        return Err(KillError::not_found());
+    }
+    policy.check_pid(&sys, pid)?;
+
+    let target = terminate(&mut sys, &[pid], grace)
+        .pop()
+        .flatten()
+        .ok_or_else(KillError::not_found)?;
+
+    let message = kill_message(&target, grace).map_err(|message| KillError::Failed { message })?;
+    Ok(KillResult {
+        pid,
+        stage: target.stage.unwrap_or(KillStage::Forced),
+        message,
+    })
+}
+
+fn kill_tree(
+    pid: u32,
+    grace: Duration,
+    policy: &ProtectionPolicy,
+) -> Result<Vec<KillOutcome>, KillError> {
+    let mut sys = System::new();
+    refresh_all(&mut sys);
+    let processes = list_processes(&sys, None, &Categorizer::default());
+    let root = processes
+        .iter()
+        .find(|p| p.pid == pid)
+        .cloned()
+        .ok_or_else(KillError::not_found)?;
+    policy.check_pid(&sys, pid)?;
+
+    let children = children_by_parent(processes);
+    let mut targets = Vec::new();
+    collect_descendants(pid, &children, &mut targets);
+    targets.push(root);
+
+    Ok(kill_targets(&mut sys, policy, targets, grace))
+}
+
+// Pid and name of each process an action was aimed at
+type Targets = Vec<(u32, String)>;
+
+// Also returns the members it found, so a refusal can be logged against
+// each of them.
+fn kill_group(
+    key: &str,
+    grace: Duration,
+    policy: &ProtectionPolicy,
+) -> (Targets, Result<Vec<KillOutcome>, KillError>) {
+    let mut sys = System::new();
+    refresh_all(&mut sys);
+    let Some(mut members) = group_processes(&sys, None, &Categorizer::default()).remove(key) else {
+        let message = "Application not found — it may have already exited".into();
+        return (Vec::new(), Err(KillError::NotFound { message }));
+    };
+    let found = members
+        .iter()
+        .map(|(p, _)| (p.pid, p.name.clone()))
+        .collect();
+
+    // As with a tree, a protected root protects the whole application.
+    for (p, _) in members.iter().filter(|(_, depth)| *depth == 0) {
+        if let Err(e) = policy.check_pid(&sys, p.pid) {
+            return (found, Err(e.into()));
         }
-    } else {
-        Err("Process not found — it may have already exited".into())
     }
+
+    // Deepest first, so no child outlives its parent.
+    members.sort_by_key(|(_, depth)| Reverse(*depth));
+    let targets = members.into_iter().map(|(p, _)| p).collect();
+    (found, Ok(kill_targets(&mut sys, policy, targets, grace)))
+}
+
+// A refused tree or group kill is logged once per process it would have hit.
+fn audit_outcomes(
+    app: &AppHandle,
+    targets: &[(u32, String)],
+    result: &Result<Vec<KillOutcome>, KillError>,
+) {
+    match result {
+        Ok(outcomes) => {
+            for o in outcomes {
+                audit::record(app, "kill", o.pid, &o.name, o.success, &o.message);
+            }
+        }
+        Err(e) => {
+            for (pid, name) in targets {
+                audit::record(app, "kill", *pid, name, false, e.message());
+            }
+        }
+    }
+}
+
+#[tauri::command(async)]
+pub fn kill_process(
+    app: AppHandle,
+    pid: u32,
+    grace_period_ms: Option<u64>,
+    policy: State<'_, PolicyState>,
+) -> Result<KillResult, KillError> {
+    let policy = policy.lock().unwrap().clone();
+    let name = audit::process_name(pid);
+    let result = kill_one(pid, grace_period(grace_period_ms), &policy);
+    let done = result
+        .as_ref()
+        .map(|r| r.message.clone())
+        .unwrap_or_default();
+    audit::record_result(&app, "kill", pid, &name, &result, &done);
+    result
+}
+
+#[tauri::command(async)]
+pub fn kill_process_tree(
+    app: AppHandle,
+    pid: u32,
+    grace_period_ms: Option<u64>,
+    policy: State<'_, PolicyState>,
+) -> Result<Vec<KillOutcome>, KillError> {
+    let policy = policy.lock().unwrap().clone();
+    let name = audit::process_name(pid);
+    let result = kill_tree(pid, grace_period(grace_period_ms), &policy);
+    audit_outcomes(&app, &[(pid, name)], &result);
+    result
+}
+
+#[tauri::command(async)]
+pub fn kill_process_group(
+    app: AppHandle,
+    key: String,
+    grace_period_ms: Option<u64>,
+    policy: State<'_, PolicyState>,
+) -> Result<Vec<KillOutcome>, KillError> {
+    let policy = policy.lock().unwrap().clone();
+    let (members, result) = kill_group(&key, grace_period(grace_period_ms), &policy);
+    audit_outcomes(&app, &members, &result);
+    result
 }
 
 #[tauri::command]
@@ -214,3 +1470,55 @@ pub fn get_vm_info() -> Vec<VmInfo> {
 
     vms
 }
+
+#[cfg(all(test, target_os = "linux"))]
+mod tests {
+    use super::*;
+
+    const ROLLUP: &str = "\
+55b3a323a000-7ffc96799000 ---p 00000000 00:00 0                          [rollup]
+Rss:                1420 kB
+Pss:                 494 kB
+Pss_Dirty:           100 kB
+Pss_Anon:            100 kB
+Pss_File:            394 kB
+Pss_Shmem:             0 kB
+Shared_Clean:       1248 kB
+Shared_Dirty:          0 kB
+Private_Clean:        72 kB
+Private_Dirty:       100 kB
+Referenced:         1420 kB
+Anonymous:           100 kB
+LazyFree:              0 kB
+AnonHugePages:         0 kB
+Swap:                 64 kB
+SwapPss:              32 kB
+Locked:                0 kB
+";
+
+    // Before 5.x kernels added the Pss_* breakdown
+    const OLD_ROLLUP: &str = "\
+00400000-7ffd4a1fe000 ---p 00000000 00:00 0                              [rollup]
+Rss:               20480 kB
+Pss:               10240 kB
+Shared_Clean:       8192 kB
+Shared_Dirty:          0 kB
+Private_Clean:      4096 kB
+Private_Dirty:      8192 kB
+Swap:                  0 kB
+SwapPss:               0 kB
+";
+
+    #[test]
+    fn parses_smaps_rollup() {
+        let cases = [
+            (ROLLUP, Some((494 * 1024, 172 * 1024, 64 * 1024))),
+            (OLD_ROLLUP, Some((10240 * 1024, 12288 * 1024, 0))),
+            ("", None),
+            ("SwapPss:              32 kB\n", None),
+        ];
+        for (text, expected) in cases {
+            assert_eq!(parse_smaps_rollup(text), expected, "{text:?}");
+        }
+    }
+}
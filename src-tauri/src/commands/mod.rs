@@ -1,5 +1,22 @@
+pub mod audit;
+pub mod categories;
+pub mod cgroups;
 pub mod cleanup;
 pub mod disk;
+pub mod flatpak;
+pub mod leaks;
+pub mod logs;
 pub mod memory;
+pub mod network;
+pub mod pkgcache;
+pub mod pressure;
+pub mod priority;
+pub mod protection;
 pub mod recommendations;
+pub mod sampler;
+pub mod snap;
 pub mod startup;
+pub mod suspend;
+pub mod systemd;
+pub mod thumbnails;
+pub mod tmpfs;
@@ -0,0 +1,367 @@
+use serde::Serialize;
+use std::collections::HashMap;
+use tauri::State;
+
+use super::memory::{CommandError, SharedSystem};
+
+#[cfg(target_os = "linux")]
+use std::collections::HashSet;
+#[cfg(target_os = "linux")]
+use std::fs;
+#[cfg(target_os = "linux")]
+use std::net::{Ipv4Addr, Ipv6Addr};
+#[cfg(target_os = "linux")]
+use sysinfo::{Pid, System};
+
+#[cfg(target_os = "linux")]
+const PROC_NET_TABLES: [&str; 4] = ["tcp", "tcp6", "udp", "udp6"];
+
+#[derive(Debug, Serialize, Clone)]
+pub struct SocketInfo {
+    pub protocol: String, // "tcp", "tcp6", "udp", "udp6", "unix"
+    pub local_address: String,
+    pub remote_address: String,
+    pub state: String,
+    pub inode: u64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct NetworkConnection {
+    #[serde(flatten)]
+    pub socket: SocketInfo,
+    pub local_port: u16,
+    pub pid: Option<u32>, // None for TIME_WAIT sockets and owners the app can't inspect
+    pub process_name: Option<String>,
+}
+
+#[cfg(target_os = "linux")]
+fn tcp_state(code: &str) -> &'static str {
+    match code {
+        "01" => "ESTABLISHED",
+        "02" => "SYN_SENT",
+        "03" => "SYN_RECV",
+        "04" => "FIN_WAIT1",
+        "05" => "FIN_WAIT2",
+        "06" => "TIME_WAIT",
+        "07" => "CLOSE",
+        "08" => "CLOSE_WAIT",
+        "09" => "LAST_ACK",
+        "0A" => "LISTEN",
+        "0B" => "CLOSING",
+        _ => "UNKNOWN",
+    }
+}
+
+// Addresses are network-order words printed as host-order hex:
+// 0100007F:0035 is 127.0.0.1:53.
+#[cfg(target_os = "linux")]
+fn parse_address(field: &str) -> Option<String> {
+    let (ip, port) = field.split_once(':')?;
+    let port = u16::from_str_radix(port, 16).ok()?;
+    let words: Vec<u32> = (0..ip.len() / 8)
+        .map(|i| u32::from_str_radix(&ip[i * 8..i * 8 + 8], 16))
+        .collect::<Result<_, _>>()
+        .ok()?;
+
+    match words.as_slice() {
+        [w] => Some(format!("{}:{port}", Ipv4Addr::from(w.to_ne_bytes()))),
+        [_, _, _, _] => {
+            let mut octets = [0u8; 16];
+            for (i, w) in words.iter().enumerate() {
+                octets[i * 4..i * 4 + 4].copy_from_slice(&w.to_ne_bytes());
+            }
+            let ip = Ipv6Addr::from(octets);
+            match ip.to_ipv4_mapped() {
+                Some(v4) => Some(format!("{v4}:{port}")),
+                None => Some(format!("[{ip}]:{port}")),
+            }
+        }
+        _ => None,
+    }
+}
+
+// sl local_address rem_address st tx_queue:rx_queue tr:tm->when retrnsmt uid timeout inode
+#[cfg(target_os = "linux")]
+fn parse_inet_table(protocol: &str, text: &str) -> Vec<SocketInfo> {
+    text.lines()
+        .skip(1)
+        .filter_map(|line| {
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            if fields.len() < 10 {
+                return None;
+            }
+            let state = if protocol.starts_with("udp") {
+                // unconnected UDP sockets report CLOSE
+                match fields[3] {
+                    "01" => "ESTABLISHED",
+                    _ => "",
+                }
+            } else {
+                tcp_state(fields[3])
+            };
+            Some(SocketInfo {
+                protocol: protocol.into(),
+                local_address: parse_address(fields[1])?,
+                remote_address: parse_address(fields[2])?,
+                state: state.into(),
+                inode: fields[9].parse().ok()?,
+            })
+        })
+        .collect()
+}
+
+// Num RefCount Protocol Flags Type St Inode [Path]
+#[cfg(target_os = "linux")]
+fn parse_unix_table(text: &str) -> Vec<SocketInfo> {
+    text.lines()
+        .skip(1)
+        .filter_map(|line| {
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            Some(SocketInfo {
+                protocol: "unix".into(),
+                local_address: fields.get(7).map(|p| p.to_string()).unwrap_or_default(),
+                remote_address: String::new(),
+                state: match *fields.get(5)? {
+                    "01" => "LISTEN",
+                    "03" => "CONNECTED",
+                    _ => "",
+                }
+                .into(),
+                inode: fields.get(6)?.parse().ok()?,
+            })
+        })
+        .collect()
+}
+
+// `dir` is /proc/net, or /proc/<pid>/net for the tables of another
+// process's network namespace.
+#[cfg(target_os = "linux")]
+fn inet_sockets_in(dir: &str) -> Vec<SocketInfo> {
+    PROC_NET_TABLES
+        .iter()
+        .flat_map(|table| {
+            let text = fs::read_to_string(format!("{dir}/{table}")).unwrap_or_default();
+            parse_inet_table(table, &text)
+        })
+        .collect()
+}
+
+#[cfg(target_os = "linux")]
+pub fn inet_sockets() -> Vec<SocketInfo> {
+    inet_sockets_in("/proc/net")
+}
+
+#[cfg(target_os = "linux")]
+fn socket_inodes(pid: u32) -> Option<Vec<u64>> {
+    let entries = fs::read_dir(format!("/proc/{pid}/fd")).ok()?;
+    Some(
+        entries
+            .flatten()
+            .filter_map(|entry| {
+                let target = fs::read_link(entry.path()).ok()?;
+                let target = target.to_str()?;
+                target
+                    .strip_prefix("socket:[")?
+                    .strip_suffix(']')?
+                    .parse()
+                    .ok()
+            })
+            .collect(),
+    )
+}
+
+// TCP and UDP sockets each process holds open, listening ones included.
+// Containers have their own tables, so sockets are looked up in the tables of
+// the process's network namespace. Processes whose fds can't be read are left
+// out.
+#[cfg(target_os = "linux")]
+pub fn connection_counts(pids: &[u32]) -> HashMap<u32, u32> {
+    let mut namespaces: HashMap<String, HashSet<u64>> = HashMap::new();
+    let mut counts = HashMap::new();
+    for &pid in pids {
+        let Some(inodes) = socket_inodes(pid) else {
+            continue;
+        };
+        let namespace = fs::read_link(format!("/proc/{pid}/ns/net"))
+            .map(|link| link.to_string_lossy().to_string())
+            .unwrap_or_default();
+        let inet = namespaces.entry(namespace).or_insert_with(|| {
+            inet_sockets_in(&format!("/proc/{pid}/net"))
+                .into_iter()
+                .map(|s| s.inode)
+                .collect()
+        });
+        let count = inodes.iter().filter(|inode| inet.contains(inode)).count();
+        counts.insert(pid, count as u32);
+    }
+    counts
+}
+
+#[cfg(target_os = "linux")]
+pub fn socket_table() -> HashMap<u64, SocketInfo> {
+    let unix = fs::read_to_string("/proc/net/unix").unwrap_or_default();
+    inet_sockets()
+        .into_iter()
+        .chain(parse_unix_table(&unix))
+        .map(|s| (s.inode, s))
+        .collect()
+}
+
+// Socket inode -> owning PID, for every process whose fds can be read. A
+// socket shared across a fork goes to the lowest PID, usually the parent.
+#[cfg(target_os = "linux")]
+fn socket_owners() -> HashMap<u64, u32> {
+    let mut owners = HashMap::new();
+    let Ok(entries) = fs::read_dir("/proc") else {
+        return owners;
+    };
+    let pids = entries
+        .flatten()
+        .filter_map(|e| e.file_name().to_str()?.parse::<u32>().ok());
+    for pid in pids {
+        for inode in socket_inodes(pid).unwrap_or_default() {
+            owners
+                .entry(inode)
+                .and_modify(|owner: &mut u32| *owner = (*owner).min(pid))
+                .or_insert(pid);
+        }
+    }
+    owners
+}
+
+#[cfg(target_os = "linux")]
+fn port(address: &str) -> u16 {
+    address
+        .rsplit_once(':')
+        .and_then(|(_, port)| port.parse().ok())
+        .unwrap_or(0)
+}
+
+// Unconnected UDP sockets are the UDP equivalent of a listening TCP socket.
+#[cfg(target_os = "linux")]
+fn is_listening(socket: &SocketInfo) -> bool {
+    socket.state == "LISTEN" || (socket.protocol.starts_with("udp") && socket.state.is_empty())
+}
+
+#[cfg(target_os = "linux")]
+fn list_connections(sys: &System) -> Vec<NetworkConnection> {
+    let owners = socket_owners();
+    let mut connections: Vec<NetworkConnection> = inet_sockets()
+        .into_iter()
+        .map(|socket| {
+            // TIME_WAIT sockets no longer belong to anyone and report inode 0
+            let pid = (socket.inode != 0)
+                .then(|| owners.get(&socket.inode).copied())
+                .flatten();
+            let process_name = pid
+                .and_then(|pid| sys.process(Pid::from_u32(pid)))
+                .map(|p| p.name().to_string_lossy().to_string());
+            NetworkConnection {
+                local_port: port(&socket.local_address),
+                socket,
+                pid,
+                process_name,
+            }
+        })
+        .collect();
+    connections.sort_by_key(|c| {
+        (
+            !is_listening(&c.socket),
+            c.local_port,
+            c.socket.protocol.clone(),
+        )
+    });
+    connections
+}
+
+// Other Unixes have no /proc/<pid>/fd, so process details list no sockets.
+#[cfg(all(unix, not(target_os = "linux")))]
+pub fn socket_table() -> HashMap<u64, SocketInfo> {
+    HashMap::new()
+}
+
+#[cfg(target_os = "linux")]
+#[tauri::command]
+pub fn get_network_connections(
+    system: State<'_, SharedSystem>,
+) -> Result<Vec<NetworkConnection>, CommandError> {
+    Ok(list_connections(&system.refreshed()))
+}
+
+#[cfg(not(target_os = "linux"))]
+#[tauri::command]
+pub fn get_network_connections(
+    _system: State<'_, SharedSystem>,
+) -> Result<Vec<NetworkConnection>, CommandError> {
+    Err(CommandError::Unsupported {
+        message: "Listing network connections needs Linux".into(),
+    })
+}
+
+// Samples are from a little-endian host; /proc prints each 32-bit word in
+// host order, so a big-endian kernel writes the same addresses differently.
+#[cfg(all(test, target_os = "linux", target_endian = "little"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_proc_net_addresses() {
+        let cases = [
+            ("0100007F:0035", Some("127.0.0.1:53")),
+            ("00000000:1F90", Some("0.0.0.0:8080")),
+            ("0101A8C0:C350", Some("192.168.1.1:50000")),
+            ("00000000000000000000000001000000:0277", Some("[::1]:631")),
+            ("00000000000000000000000000000000:0016", Some("[::]:22")),
+            (
+                "0000000000000000FFFF00000100007F:1F90",
+                Some("127.0.0.1:8080"),
+            ),
+            (
+                "000080FE000000004D3C2B1A81706F5E:14E9",
+                Some("[fe80::1a2b:3c4d:5e6f:7081]:5353"),
+            ),
+            (
+                "B80D0120000000002E8A000034737003:01BB",
+                Some("[2001:db8::8a2e:370:7334]:443"),
+            ),
+            ("0100007F", None),
+            ("0100007G:0035", None),
+            ("0100007F:XYZ", None),
+            ("01000:0035", None),
+            ("0000000000000000:0035", None),
+        ];
+        for (field, expected) in cases {
+            assert_eq!(parse_address(field).as_deref(), expected, "{field:?}");
+        }
+    }
+
+    #[test]
+    fn parses_inet_table_lines() {
+        let text = "\
+  sl  local_address rem_address   st tx_queue rx_queue tr tm->when retrnsmt   uid  timeout inode
+   0: 0100007F:0277 00000000:0000 0A 00000000:00000000 00:00000000 00000000     0        0 23456 1 0000000000000000 100 0 0 10 0
+   1: 0101A8C0:C350 2208B85D:01BB 01 00000000:00000000 02:000A7D8B 00000000  1000        0 98765 2 0000000000000000 20 4 30 10 -1
+   2: 0101A8C0:C352 2208B85D:01BB 06 00000000:00000000 03:00001234 00000000     0        0 0 3 0000000000000000
+";
+        let sockets = parse_inet_table("tcp", text);
+        let summary: Vec<_> = sockets
+            .iter()
+            .map(|s| {
+                (
+                    s.local_address.as_str(),
+                    s.remote_address.as_str(),
+                    s.state.as_str(),
+                    s.inode,
+                )
+            })
+            .collect();
+        assert_eq!(
+            summary,
+            [
+                ("127.0.0.1:631", "0.0.0.0:0", "LISTEN", 23456),
+                ("192.168.1.1:50000", "93.184.8.34:443", "ESTABLISHED", 98765),
+                ("192.168.1.1:50002", "93.184.8.34:443", "TIME_WAIT", 0),
+            ]
+        );
+    }
+}
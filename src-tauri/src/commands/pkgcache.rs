@@ -0,0 +1,478 @@
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use super::cleanup::{run_privileged, run_privileged_with_input};
+use super::disk::CleanableItem;
+
+const APT_ARCHIVES: &str = "/var/cache/apt/archives";
+const DNF_CACHES: [&str; 3] = ["/var/cache/libdnf5", "/var/cache/dnf", "/var/cache/yum"];
+const PACMAN_CACHE: &str = "/var/cache/pacman/pkg";
+const ZYPPER_CACHE: &str = "/var/cache/zypp/packages";
+const PACMAN_KEEP_VERSIONS: usize = 3;
+
+enum Cleaner {
+    Tool(&'static str, &'static [&'static str]),
+    Files(Vec<PathBuf>),
+}
+
+struct PackageCache {
+    id: &'static str,
+    name: &'static str,
+    description: String,
+    path: PathBuf,
+    size: u64,
+    cleaner: Cleaner,
+}
+
+fn has_tool(tool: &str) -> bool {
+    env::var_os("PATH")
+        .map(|paths| env::split_paths(&paths).any(|dir| dir.join(tool).is_file()))
+        .unwrap_or(false)
+}
+
+fn dir_size(path: &Path) -> u64 {
+    let mut total = 0u64;
+    if let Ok(entries) = fs::read_dir(path) {
+        for entry in entries.flatten() {
+            let p = entry.path();
+            if p.is_file() {
+                total += entry.metadata().map(|m| m.len()).unwrap_or(0);
+            } else if p.is_dir() {
+                total += dir_size(&p);
+            }
+        }
+    }
+    total
+}
+
+fn apt_cache() -> Option<PackageCache> {
+    if !has_tool("apt-get") {
+        return None;
+    }
+    let mut size = 0u64;
+    let mut count = 0u32;
+    for entry in fs::read_dir(APT_ARCHIVES).ok()?.flatten() {
+        if entry.file_name().to_string_lossy().ends_with(".deb") {
+            size += entry.metadata().map(|m| m.len()).unwrap_or(0);
+            count += 1;
+        }
+    }
+    Some(PackageCache {
+        id: "apt_cache",
+        name: "APT Package Cache",
+        description: format!("{count} downloaded .deb files — removed with apt-get clean"),
+        path: PathBuf::from(APT_ARCHIVES),
+        size,
+        cleaner: Cleaner::Tool("apt-get", &["clean"]),
+    })
+}
+
+fn dnf_cache() -> Option<PackageCache> {
+    let (tool, args): (&'static str, &'static [&'static str]) = if has_tool("dnf") {
+        ("dnf", &["clean", "all"])
+    } else if has_tool("yum") {
+        ("yum", &["clean", "all"])
+    } else {
+        return None;
+    };
+    let path = DNF_CACHES.iter().map(Path::new).find(|p| p.is_dir())?;
+    Some(PackageCache {
+        id: "dnf_cache",
+        name: if tool == "dnf" {
+            "DNF Package Cache"
+        } else {
+            "Yum Package Cache"
+        },
+        description: format!(
+            "Cached packages and repository metadata — removed with {tool} clean all"
+        ),
+        path: path.to_path_buf(),
+        size: dir_size(path),
+        cleaner: Cleaner::Tool(tool, args),
+    })
+}
+
+fn zypper_cache() -> Option<PackageCache> {
+    if !has_tool("zypper") {
+        return None;
+    }
+    let path = Path::new(ZYPPER_CACHE);
+    if !path.is_dir() {
+        return None;
+    }
+    Some(PackageCache {
+        id: "zypper_cache",
+        name: "Zypper Package Cache",
+        description: "Downloaded .rpm files — removed with zypper clean".into(),
+        path: path.to_path_buf(),
+        size: dir_size(path),
+        cleaner: Cleaner::Tool("zypper", &["clean"]),
+    })
+}
+
+fn trim_zeros(s: &[u8]) -> &[u8] {
+    &s[s.iter().position(|&c| c != b'0').unwrap_or(s.len())..]
+}
+
+// rpmvercmp as pacman ships it (libalpm/version.c). Segments are runs of
+// digits or letters; the separators between them only matter by length.
+fn compare_segments(a: &str, b: &str) -> Ordering {
+    if a == b {
+        return Ordering::Equal;
+    }
+    let (a, b) = (a.as_bytes(), b.as_bytes());
+    let (mut i, mut j) = (0, 0);
+
+    while i < a.len() && j < b.len() {
+        let (sep_a, sep_b) = (i, j);
+        while i < a.len() && !a[i].is_ascii_alphanumeric() {
+            i += 1;
+        }
+        while j < b.len() && !b[j].is_ascii_alphanumeric() {
+            j += 1;
+        }
+        if i == a.len() || j == b.len() {
+            break;
+        }
+        // 1.0a < 1.0.a
+        if i - sep_a != j - sep_b {
+            return (i - sep_a).cmp(&(j - sep_b));
+        }
+
+        let numeric = a[i].is_ascii_digit();
+        let run = |s: &[u8], start: usize| {
+            start
+                + s[start..]
+                    .iter()
+                    .take_while(|c| {
+                        if numeric {
+                            c.is_ascii_digit()
+                        } else {
+                            c.is_ascii_alphabetic()
+                        }
+                    })
+                    .count()
+        };
+        let (end_a, end_b) = (run(a, i), run(b, j));
+        // A number against letters: the number is newer
+        if end_b == j {
+            return if numeric {
+                Ordering::Greater
+            } else {
+                Ordering::Less
+            };
+        }
+
+        let (seg_a, seg_b) = (&a[i..end_a], &b[j..end_b]);
+        let ord = if numeric {
+            let (ta, tb) = (trim_zeros(seg_a), trim_zeros(seg_b));
+            ta.len().cmp(&tb.len()).then_with(|| ta.cmp(tb))
+        } else {
+            seg_a.cmp(seg_b)
+        };
+        if ord != Ordering::Equal {
+            return ord;
+        }
+        i = end_a;
+        j = end_b;
+    }
+
+    let (rest_a, rest_b) = (&a[i..], &b[j..]);
+    if rest_a.is_empty() && rest_b.is_empty() {
+        return Ordering::Equal;
+    }
+    // A trailing letter segment marks a pre-release: 1.0rc1 < 1.0 < 1.0.1
+    let alpha = |s: &[u8]| s.first().is_some_and(u8::is_ascii_alphabetic);
+    if (rest_a.is_empty() && !alpha(rest_b)) || alpha(rest_a) {
+        Ordering::Less
+    } else {
+        Ordering::Greater
+    }
+}
+
+// [epoch:]pkgver[-pkgrel], split the way pacman's parseEVR does
+fn split_version(v: &str) -> (&str, &str, Option<&str>) {
+    let digits = v.bytes().take_while(u8::is_ascii_digit).count();
+    let (epoch, rest) = match v[digits..].strip_prefix(':') {
+        Some(rest) if digits > 0 => (&v[..digits], rest),
+        Some(rest) => ("0", rest),
+        None => ("0", v),
+    };
+    match rest.rsplit_once('-') {
+        Some((ver, rel)) => (epoch, ver, Some(rel)),
+        None => (epoch, rest, None),
+    }
+}
+
+// pkgrel only counts when both sides have one, as in alpm_pkg_vercmp.
+fn compare_versions(a: &str, b: &str) -> Ordering {
+    let (ea, va, ra) = split_version(a);
+    let (eb, vb, rb) = split_version(b);
+    compare_segments(ea, eb)
+        .then_with(|| compare_segments(va, vb))
+        .then_with(|| match (ra, rb) {
+            (Some(ra), Some(rb)) => compare_segments(ra, rb),
+            _ => Ordering::Equal,
+        })
+}
+
+// Everything but the newest `keep` versions of one package.
+fn superseded<T>(mut versions: Vec<(String, T)>, keep: usize) -> Vec<T> {
+    versions.sort_by(|a, b| compare_versions(&b.0, &a.0));
+    versions.into_iter().skip(keep).map(|(_, v)| v).collect()
+}
+
+// <name>-<pkgver>-<pkgrel>-<arch>.pkg.tar.<ext>
+fn parse_pacman_file(file_name: &str) -> Option<(String, String)> {
+    let stem = &file_name[..file_name.find(".pkg.tar")?];
+    let mut parts = stem.rsplitn(4, '-');
+    let _arch = parts.next()?;
+    let rel = parts.next()?;
+    let ver = parts.next()?;
+    let name = parts.next()?;
+    Some((name.to_string(), format!("{ver}-{rel}")))
+}
+
+fn pacman_cache() -> Option<PackageCache> {
+    if !has_tool("pacman") {
+        return None;
+    }
+
+    // (version, (file, size)) of each cached version
+    type Versions = Vec<(String, (PathBuf, u64))>;
+    let mut packages: HashMap<String, Versions> = HashMap::new();
+    for entry in fs::read_dir(PACMAN_CACHE).ok()?.flatten() {
+        let file_name = entry.file_name().to_string_lossy().to_string();
+        if file_name.ends_with(".sig") {
+            continue;
+        }
+        if let Some((name, version)) = parse_pacman_file(&file_name) {
+            let size = entry.metadata().map(|m| m.len()).unwrap_or(0);
+            packages
+                .entry(name)
+                .or_default()
+                .push((version, (entry.path(), size)));
+        }
+    }
+
+    let mut old_files = Vec::new();
+    let mut size = 0u64;
+    let mut count = 0u32;
+    for versions in packages.into_values() {
+        for (path, pkg_size) in superseded(versions, PACMAN_KEEP_VERSIONS) {
+            size += pkg_size;
+            count += 1;
+            old_files.push(path.clone());
+
+            let mut sig = path.as_os_str().to_os_string();
+            sig.push(".sig");
+            let sig = PathBuf::from(sig);
+            if let Ok(meta) = sig.metadata() {
+                size += meta.len();
+                old_files.push(sig);
+            }
+        }
+    }
+
+    Some(PackageCache {
+        id: "pacman_cache",
+        name: "Pacman Package Cache",
+        description: format!(
+            "{count} old package versions beyond the newest {PACMAN_KEEP_VERSIONS} of each, like paccache -rk{PACMAN_KEEP_VERSIONS}"
+        ),
+        path: PathBuf::from(PACMAN_CACHE),
+        size,
+        cleaner: Cleaner::Files(old_files),
+    })
+}
+
+fn list_caches() -> Vec<PackageCache> {
+    [apt_cache(), dnf_cache(), pacman_cache(), zypper_cache()]
+        .into_iter()
+        .flatten()
+        .collect()
+}
+
+pub fn scan_package_caches() -> Vec<CleanableItem> {
+    list_caches()
+        .into_iter()
+        .filter(|c| c.size > 1_000_000)
+        .map(|c| CleanableItem {
+            id: c.id.into(),
+            name: c.name.into(),
+            description: c.description,
+            size_bytes: c.size,
+            path: c.path.to_string_lossy().into(),
+            risk: "moderate".into(),
+            category: "System".into(),
+            requires_elevation: true,
+        })
+        .collect()
+}
+
+pub fn clean_package_cache(id: &str) -> (u64, String) {
+    let cache = match list_caches().into_iter().find(|c| c.id == id) {
+        Some(c) => c,
+        None => return (0, "Package manager not found".into()),
+    };
+
+    let (success, output) = match &cache.cleaner {
+        Cleaner::Tool(tool, args) => run_privileged(tool, args),
+        Cleaner::Files(files) => {
+            // NUL-separated on stdin, so the whole list is one privileged call
+            let mut input = Vec::new();
+            for path in files {
+                input.extend_from_slice(path.to_string_lossy().as_bytes());
+                input.push(0);
+            }
+            run_privileged_with_input("xargs", &["-0", "-r", "rm", "-f", "--"], &input)
+        }
+    };
+
+    let after = list_caches()
+        .into_iter()
+        .find(|c| c.id == id)
+        .map(|c| c.size)
+        .unwrap_or(0);
+    let freed = cache.size.saturating_sub(after);
+
+    if success {
+        (
+            freed,
+            format!("Cleaned successfully\n{output}").trim().into(),
+        )
+    } else {
+        (freed, format!("Failed: {output}"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cmp::Ordering::{Equal, Greater, Less};
+
+    // From pacman's test/util/vercmptest.sh
+    #[test]
+    fn compares_versions_like_vercmp() {
+        let cases = [
+            ("1.5.0", "1.5.0", Equal),
+            ("1.5.1", "1.5.0", Greater),
+            ("1.5.1", "1.5", Greater),
+            // pkgrel
+            ("1.5.0-1", "1.5.0-2", Less),
+            ("1.5.0-2", "1.5.1-1", Less),
+            ("1.5-2", "1.5.1-1", Less),
+            ("1.0-10", "1.0-9", Greater),
+            ("1.5", "1.5-1", Equal),
+            ("1.1-1", "1.0", Greater),
+            // letters
+            ("1.5b-1", "1.5-1", Less),
+            ("1.5b", "1.5.1", Less),
+            ("1.0a", "1.0alpha", Less),
+            ("1.0alpha", "1.0b", Less),
+            ("1.0beta", "1.0rc", Less),
+            ("1.0rc", "1.0", Less),
+            ("1.0rc1", "1.0rc2", Less),
+            ("1.5.a", "1.5", Greater),
+            ("1.5.b", "1.5.a", Greater),
+            ("1.5.1", "1.5.b", Greater),
+            ("1.5.b-1", "1.5.b", Equal),
+            ("1.5-1", "1.5.b", Less),
+            ("1a", "1", Less),
+            ("a1", "1", Less),
+            // separators count only by length
+            ("2.0", "2_0", Equal),
+            ("2.0_a", "2_0.a", Equal),
+            ("2.0a", "2.0.a", Less),
+            ("1.0a", "1.0.a", Less),
+            ("2___a", "2_a", Greater),
+            // leading zeros
+            ("1.010", "1.9", Greater),
+            ("1.001", "1.1", Equal),
+            // epoch
+            ("0:1.0", "0:1.1", Less),
+            ("1:1.0", "0:1.1", Greater),
+            ("1:1.0", "2:1.1", Less),
+            ("10:1.0", "9:2.0", Greater),
+            ("1:1.0", "0:1.0-1", Greater),
+            ("0:1.0", "1.0", Equal),
+            ("1.1", "1:1.1", Less),
+            ("1:1.0-1", "2.0-5", Greater),
+            // git snapshots
+            ("r1234.abcdef-1", "r1235.abc-1", Less),
+            ("1.2.3.r4.g5678-1", "1.2.3-1", Greater),
+        ];
+        for (a, b, expected) in cases {
+            assert_eq!(compare_versions(a, b), expected, "{a} vs {b}");
+            assert_eq!(compare_versions(b, a), expected.reverse(), "{b} vs {a}");
+        }
+    }
+
+    #[test]
+    fn parses_package_file_names() {
+        let cases = [
+            (
+                "linux-6.9.7.arch1-1-x86_64.pkg.tar.zst",
+                Some(("linux", "6.9.7.arch1-1")),
+            ),
+            (
+                "python-pip-24.0-2-any.pkg.tar.zst",
+                Some(("python-pip", "24.0-2")),
+            ),
+            (
+                "lib32-mesa-vdpau-1:24.1.2-1-x86_64.pkg.tar.xz",
+                Some(("lib32-mesa-vdpau", "1:24.1.2-1")),
+            ),
+            (
+                "gtk-update-icon-cache-1:4.14.4-1-x86_64.pkg.tar.zst",
+                Some(("gtk-update-icon-cache", "1:4.14.4-1")),
+            ),
+            ("README", None),
+            ("zstd-1.5.6-1.pkg.tar.zst", None),
+        ];
+        for (file, expected) in cases {
+            let parsed = parse_pacman_file(file);
+            assert_eq!(
+                parsed.as_ref().map(|(n, v)| (n.as_str(), v.as_str())),
+                expected,
+                "{file}"
+            );
+        }
+    }
+
+    #[test]
+    fn keeps_the_newest_versions_like_paccache() {
+        let files = [
+            "firefox-127.0-1-x86_64.pkg.tar.zst",
+            "firefox-127.0.2-1-x86_64.pkg.tar.zst",
+            "firefox-128.0-1-x86_64.pkg.tar.zst",
+            "firefox-126.0.1-1-x86_64.pkg.tar.zst",
+            "firefox-127.0.2-2-x86_64.pkg.tar.zst",
+            "firefox-128.0b9-1-x86_64.pkg.tar.zst",
+        ];
+        let versions = files
+            .iter()
+            .map(|f| (parse_pacman_file(f).unwrap().1, *f))
+            .collect();
+        let mut old = superseded(versions, 3);
+        old.sort();
+        assert_eq!(
+            old,
+            [
+                "firefox-126.0.1-1-x86_64.pkg.tar.zst",
+                "firefox-127.0-1-x86_64.pkg.tar.zst",
+                "firefox-127.0.2-1-x86_64.pkg.tar.zst",
+            ]
+        );
+
+        // an epoch bump outranks any version without one
+        let versions = vec![
+            ("2:1.0-1".to_string(), "epoch"),
+            ("99.0-1".to_string(), "plain"),
+        ];
+        assert_eq!(superseded(versions, 1), ["plain"]);
+    }
+}
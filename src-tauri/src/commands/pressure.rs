@@ -0,0 +1,240 @@
+use serde::Serialize;
+use std::cmp::Reverse;
+
+#[cfg(target_os = "linux")]
+use std::process::Command;
+#[cfg(target_os = "linux")]
+use sysinfo::System;
+
+const DEFAULT_OOM_LIMIT: usize = 50;
+
+// Share of wall time, in percent, that tasks spent stalled waiting for
+// memory, averaged over 10 s, 60 s and 300 s. "some" means at least one task
+// was stalled; "full" means every non-idle task was, so nothing got done.
+#[derive(Debug, Serialize, Clone, Copy, Default)]
+pub struct MemoryPressure {
+    pub some_avg10: f64,
+    pub some_avg60: f64,
+    pub some_avg300: f64,
+    pub full_avg10: f64,
+    pub full_avg60: f64,
+    pub full_avg300: f64,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct OomEvent {
+    pub timestamp: u64,
+    pub pid: u32,
+    pub name: String,
+    pub rss_bytes: u64, // anon + file + shmem RSS at the time of the kill
+}
+
+#[derive(Debug, Serialize)]
+pub struct OomReport {
+    pub events: Vec<OomEvent>,
+    // "journal" or "dmesg"; "unavailable" when neither could be read (no
+    // journal access and dmesg_restrict=1), "unsupported" off Linux
+    pub source: String,
+}
+
+// Needs Linux 4.20 with PSI enabled; some distros boot with psi=0.
+#[cfg(target_os = "linux")]
+pub fn read_memory_pressure() -> Option<MemoryPressure> {
+    let text = std::fs::read_to_string("/proc/pressure/memory").ok()?;
+    let mut pressure = MemoryPressure::default();
+    for line in text.lines() {
+        let Some((kind, fields)) = line.split_once(' ') else {
+            continue;
+        };
+        let avg = |key: &str| -> f64 {
+            fields
+                .split_whitespace()
+                .find_map(|field| field.strip_prefix(key)?.strip_prefix('='))
+                .and_then(|value| value.parse().ok())
+                .unwrap_or(0.0)
+        };
+        let (avg10, avg60, avg300) = (avg("avg10"), avg("avg60"), avg("avg300"));
+        match kind {
+            "some" => {
+                pressure.some_avg10 = avg10;
+                pressure.some_avg60 = avg60;
+                pressure.some_avg300 = avg300;
+            }
+            "full" => {
+                pressure.full_avg10 = avg10;
+                pressure.full_avg60 = avg60;
+                pressure.full_avg300 = avg300;
+            }
+            _ => {}
+        }
+    }
+    Some(pressure)
+}
+
+#[cfg(not(target_os = "linux"))]
+pub fn read_memory_pressure() -> Option<MemoryPressure> {
+    None
+}
+
+// The kernel logs one line per victim, e.g.
+// "Out of memory: Killed process 1234 (chrome) total-vm:..kB, anon-rss:..kB, file-rss:..kB, shmem-rss:..kB, UID:1000 ..."
+// Cgroup limits log "Memory cgroup out of memory: Killed process ..." instead.
+#[cfg(target_os = "linux")]
+fn parse_oom_line(timestamp: u64, line: &str) -> Option<OomEvent> {
+    let (_, rest) = line.split_once("Killed process ")?;
+    let (pid, rest) = rest.split_once(" (")?;
+    let (name, rest) = rest.split_once(") ")?;
+    let rss_kb: u64 = rest
+        .split([' ', ','])
+        .filter_map(|field| {
+            let (key, value) = field.split_once(':')?;
+            key.ends_with("-rss").then_some(value)
+        })
+        .filter_map(|value| value.trim_end_matches("kB").parse::<u64>().ok())
+        .sum();
+    Some(OomEvent {
+        timestamp,
+        pid: pid.trim().parse().ok()?,
+        name: name.to_string(),
+        rss_bytes: rss_kb * 1024,
+    })
+}
+
+// "1697040000.123456 host kernel: Out of memory: Killed process ..."
+// None when journalctl is missing or may only read the user's own journal,
+// which never holds kernel messages.
+#[cfg(target_os = "linux")]
+fn journal_oom_events() -> Option<Vec<OomEvent>> {
+    let output = Command::new("journalctl")
+        .args([
+            "_TRANSPORT=kernel",
+            "--since=-7d",
+            "-o",
+            "short-unix",
+            "--no-pager",
+        ])
+        .env("LC_ALL", "C")
+        .output()
+        .ok()?;
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    if !output.status.success()
+        || stderr.contains("insufficient permissions")
+        || stderr.contains("not seeing messages from other users and the system")
+    {
+        return None;
+    }
+    let text = String::from_utf8_lossy(&output.stdout);
+    Some(
+        text.lines()
+            .filter_map(|line| {
+                let (stamp, message) = line.split_once(' ')?;
+                let (secs, _) = stamp.split_once('.').unwrap_or((stamp, ""));
+                parse_oom_line(secs.parse().ok()?, message)
+            })
+            .collect(),
+    )
+}
+
+// Without journald only the ring buffer is left, which is stamped in seconds
+// since boot and loses old lines once it fills up. None when
+// kernel.dmesg_restrict keeps it from unprivileged users.
+#[cfg(target_os = "linux")]
+fn dmesg_oom_events() -> Option<Vec<OomEvent>> {
+    let output = Command::new("dmesg").output().ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    let boot = System::boot_time();
+    Some(
+        String::from_utf8_lossy(&output.stdout)
+            .lines()
+            .filter_map(|line| {
+                let (stamp, message) = line.strip_prefix('[')?.split_once(']')?;
+                let since_boot: f64 = stamp.trim().parse().ok()?;
+                parse_oom_line(boot + since_boot as u64, message)
+            })
+            .collect(),
+    )
+}
+
+#[cfg(target_os = "linux")]
+fn read_oom_events() -> OomReport {
+    let report = |events, source: &str| OomReport {
+        events,
+        source: source.into(),
+    };
+    match journal_oom_events() {
+        Some(events) if !events.is_empty() => report(events, "journal"),
+        journal => match (dmesg_oom_events(), journal) {
+            (Some(events), _) => report(events, "dmesg"),
+            (None, Some(events)) => report(events, "journal"),
+            (None, None) => report(Vec::new(), "unavailable"),
+        },
+    }
+}
+
+#[cfg(not(target_os = "linux"))]
+fn read_oom_events() -> OomReport {
+    OomReport {
+        events: Vec::new(),
+        source: "unsupported".into(),
+    }
+}
+
+#[tauri::command]
+pub fn get_oom_events(limit: Option<usize>) -> OomReport {
+    let mut report = read_oom_events();
+    report.events.sort_by_key(|e| Reverse(e.timestamp));
+    report.events.truncate(limit.unwrap_or(DEFAULT_OOM_LIMIT));
+    report
+}
+
+#[cfg(all(test, target_os = "linux"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_oom_kill_lines() {
+        let cases = [
+            (
+                "[12345.678901] Out of memory: Killed process 4242 (chrome) total-vm:12345678kB, anon-rss:1048576kB, file-rss:2048kB, shmem-rss:1024kB, UID:1000 pgtables:4096kB oom_score_adj:300",
+                Some((4242, "chrome", 1_051_648)),
+            ),
+            (
+                "Memory cgroup out of memory: Killed process 777 (Web Content) total-vm:2600000kB, anon-rss:100kB, file-rss:0kB, shmem-rss:0kB, UID:1000 pgtables:1200kB oom_score_adj:0",
+                Some((777, "Web Content", 100)),
+            ),
+            // 4.x kernels log no shmem-rss or UID
+            (
+                "Out of memory: Killed process 1234 (java) total-vm:2000kB, anon-rss:500kB, file-rss:20kB",
+                Some((1234, "java", 520)),
+            ),
+            (
+                "Out of memory: Killed process 1 ((sd-pam)) total-vm:100kB, anon-rss:8kB, file-rss:0kB, shmem-rss:0kB, UID:0",
+                Some((1, "(sd-pam)", 8)),
+            ),
+            (
+                "oom_reaper: reaped process 4242 (chrome), now anon-rss:0kB, file-rss:0kB, shmem-rss:0kB",
+                None,
+            ),
+            (
+                "Out of memory: Kill process 1234 (java) score 900 or sacrifice child",
+                None,
+            ),
+            ("Out of memory: Killed process abc (x) anon-rss:1kB", None),
+        ];
+        for (line, expected) in cases {
+            let event = parse_oom_line(1_700_000_000, line);
+            assert_eq!(
+                event
+                    .as_ref()
+                    .map(|e| (e.pid, e.name.as_str(), e.rss_bytes / 1024)),
+                expected,
+                "{line:?}"
+            );
+            if let Some(event) = event {
+                assert_eq!(event.timestamp, 1_700_000_000);
+            }
+        }
+    }
+}
@@ -0,0 +1,290 @@
+use serde::{Deserialize, Serialize};
+use sysinfo::Pid;
+use tauri::{AppHandle, State};
+
+use super::audit;
+use super::memory::{KillError, SharedSystem};
+use super::protection::PolicyState;
+
+#[cfg(target_os = "linux")]
+use std::fs;
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum PriorityLevel {
+    Idle,
+    BelowNormal,
+    Normal,
+    AboveNormal,
+    High,
+}
+
+#[cfg(unix)]
+impl PriorityLevel {
+    fn nice(self) -> i32 {
+        match self {
+            PriorityLevel::Idle => 19,
+            PriorityLevel::BelowNormal => 10,
+            PriorityLevel::Normal => 0,
+            PriorityLevel::AboveNormal => -5,
+            PriorityLevel::High => -10,
+        }
+    }
+
+    #[cfg(target_os = "linux")]
+    fn from_nice(nice: i32) -> Self {
+        match nice {
+            15.. => PriorityLevel::Idle,
+            5..=14 => PriorityLevel::BelowNormal,
+            -4..=4 => PriorityLevel::Normal,
+            -9..=-5 => PriorityLevel::AboveNormal,
+            _ => PriorityLevel::High,
+        }
+    }
+}
+
+// Linux keeps nice values and affinity per thread, so changes are applied to
+// every task of the process. Threads started later inherit from the thread
+// that creates them.
+#[cfg(target_os = "linux")]
+fn tasks(pid: u32) -> Vec<u32> {
+    let tasks: Vec<u32> = fs::read_dir(format!("/proc/{pid}/task"))
+        .map(|dir| {
+            dir.flatten()
+                .filter_map(|e| e.file_name().to_str()?.parse().ok())
+                .collect()
+        })
+        .unwrap_or_default();
+    if tasks.is_empty() {
+        vec![pid]
+    } else {
+        tasks
+    }
+}
+
+#[cfg(not(target_os = "linux"))]
+#[cfg(unix)]
+fn tasks(pid: u32) -> Vec<u32> {
+    vec![pid]
+}
+
+#[cfg(target_os = "linux")]
+pub fn get_priority(pid: u32) -> Option<PriorityLevel> {
+    // Field 19 of stat; the name in field 2 may itself contain spaces.
+    let stat = fs::read_to_string(format!("/proc/{pid}/stat")).ok()?;
+    let (_, fields) = stat.rsplit_once(')')?;
+    let nice: i32 = fields.split_whitespace().nth(16)?.parse().ok()?;
+    Some(PriorityLevel::from_nice(nice))
+}
+
+#[cfg(target_os = "linux")]
+pub fn get_affinity(pid: u32) -> Option<Vec<u32>> {
+    unsafe {
+        let mut set: libc::cpu_set_t = std::mem::zeroed();
+        if libc::sched_getaffinity(pid as libc::pid_t, std::mem::size_of_val(&set), &mut set) != 0 {
+            return None;
+        }
+        Some(
+            (0..libc::CPU_SETSIZE as usize)
+                .filter(|&cpu| libc::CPU_ISSET(cpu, &set))
+                .map(|cpu| cpu as u32)
+                .collect(),
+        )
+    }
+}
+
+#[cfg(unix)]
+fn apply_priority(pid: u32, level: PriorityLevel) -> Result<(), String> {
+    for task in tasks(pid) {
+        let rc = unsafe { libc::setpriority(libc::PRIO_PROCESS, task as libc::id_t, level.nice()) };
+        if rc != 0 {
+            return Err(std::io::Error::last_os_error().to_string());
+        }
+    }
+    Ok(())
+}
+
+#[cfg(target_os = "linux")]
+fn apply_affinity(pid: u32, cpus: &[u32]) -> Result<(), String> {
+    let mut set: libc::cpu_set_t = unsafe { std::mem::zeroed() };
+    for &cpu in cpus {
+        if cpu as usize >= libc::CPU_SETSIZE as usize {
+            return Err(format!("CPU {cpu} does not exist"));
+        }
+        unsafe { libc::CPU_SET(cpu as usize, &mut set) };
+    }
+    for task in tasks(pid) {
+        let rc = unsafe {
+            libc::sched_setaffinity(task as libc::pid_t, std::mem::size_of_val(&set), &set)
+        };
+        if rc != 0 {
+            return Err(std::io::Error::last_os_error().to_string());
+        }
+    }
+    Ok(())
+}
+
+#[cfg(windows)]
+mod win {
+    use super::PriorityLevel;
+    use windows::Win32::Foundation::{CloseHandle, HANDLE};
+    use windows::Win32::System::Threading::{
+        GetPriorityClass, GetProcessAffinityMask, OpenProcess, SetPriorityClass,
+        SetProcessAffinityMask, ABOVE_NORMAL_PRIORITY_CLASS, BELOW_NORMAL_PRIORITY_CLASS,
+        HIGH_PRIORITY_CLASS, IDLE_PRIORITY_CLASS, NORMAL_PRIORITY_CLASS, PROCESS_ACCESS_RIGHTS,
+        PROCESS_CREATION_FLAGS, PROCESS_QUERY_LIMITED_INFORMATION, PROCESS_SET_INFORMATION,
+        REALTIME_PRIORITY_CLASS,
+    };
+
+    fn with_process<T>(
+        pid: u32,
+        access: PROCESS_ACCESS_RIGHTS,
+        f: impl FnOnce(HANDLE) -> windows::core::Result<T>,
+    ) -> Result<T, String> {
+        unsafe {
+            let handle = OpenProcess(access, false, pid).map_err(|e| e.message())?;
+            let result = f(handle).map_err(|e| e.message());
+            let _ = CloseHandle(handle);
+            result
+        }
+    }
+
+    fn class(level: PriorityLevel) -> PROCESS_CREATION_FLAGS {
+        match level {
+            PriorityLevel::Idle => IDLE_PRIORITY_CLASS,
+            PriorityLevel::BelowNormal => BELOW_NORMAL_PRIORITY_CLASS,
+            PriorityLevel::Normal => NORMAL_PRIORITY_CLASS,
+            PriorityLevel::AboveNormal => ABOVE_NORMAL_PRIORITY_CLASS,
+            PriorityLevel::High => HIGH_PRIORITY_CLASS,
+        }
+    }
+
+    pub fn get_priority(pid: u32) -> Option<PriorityLevel> {
+        let raw = with_process(pid, PROCESS_QUERY_LIMITED_INFORMATION, |h| unsafe {
+            Ok(GetPriorityClass(h))
+        })
+        .ok()?;
+        match PROCESS_CREATION_FLAGS(raw) {
+            IDLE_PRIORITY_CLASS => Some(PriorityLevel::Idle),
+            BELOW_NORMAL_PRIORITY_CLASS => Some(PriorityLevel::BelowNormal),
+            NORMAL_PRIORITY_CLASS => Some(PriorityLevel::Normal),
+            ABOVE_NORMAL_PRIORITY_CLASS => Some(PriorityLevel::AboveNormal),
+            HIGH_PRIORITY_CLASS | REALTIME_PRIORITY_CLASS => Some(PriorityLevel::High),
+            _ => None,
+        }
+    }
+
+    pub fn get_affinity(pid: u32) -> Option<Vec<u32>> {
+        let mask = with_process(pid, PROCESS_QUERY_LIMITED_INFORMATION, |h| unsafe {
+            let (mut process, mut system) = (0usize, 0usize);
+            GetProcessAffinityMask(h, &mut process, &mut system).map(|_| process)
+        })
+        .ok()?;
+        Some(
+            (0..usize::BITS)
+                .filter(|&cpu| mask & (1 << cpu) != 0)
+                .collect(),
+        )
+    }
+
+    pub fn apply_priority(pid: u32, level: PriorityLevel) -> Result<(), String> {
+        with_process(pid, PROCESS_SET_INFORMATION, |h| unsafe {
+            SetPriorityClass(h, class(level))
+        })
+    }
+
+    pub fn apply_affinity(pid: u32, cpus: &[u32]) -> Result<(), String> {
+        let mut mask = 0usize;
+        for &cpu in cpus {
+            if cpu >= usize::BITS {
+                return Err(format!("CPU {cpu} does not exist"));
+            }
+            mask |= 1 << cpu;
+        }
+        with_process(pid, PROCESS_SET_INFORMATION, |h| unsafe {
+            SetProcessAffinityMask(h, mask)
+        })
+    }
+}
+
+#[cfg(windows)]
+use win::{apply_affinity, apply_priority};
+#[cfg(windows)]
+pub use win::{get_affinity, get_priority};
+
+#[cfg(not(any(target_os = "linux", windows)))]
+pub fn get_priority(_pid: u32) -> Option<PriorityLevel> {
+    None
+}
+
+#[cfg(not(any(target_os = "linux", windows)))]
+pub fn get_affinity(_pid: u32) -> Option<Vec<u32>> {
+    None
+}
+
+#[cfg(not(any(unix, windows)))]
+fn apply_priority(_pid: u32, _level: PriorityLevel) -> Result<(), String> {
+    Err("Changing priority is not supported on this platform".into())
+}
+
+#[cfg(not(any(target_os = "linux", windows)))]
+fn apply_affinity(_pid: u32, _cpus: &[u32]) -> Result<(), String> {
+    Err("Changing CPU affinity is not supported on this platform".into())
+}
+
+fn checked(
+    policy: &State<'_, PolicyState>,
+    system: &SharedSystem,
+    pid: u32,
+) -> Result<(), KillError> {
+    let policy = policy.lock().unwrap().clone();
+    let sys = system.refreshed();
+    let process = sys
+        .process(Pid::from_u32(pid))
+        .ok_or_else(KillError::not_found)?;
+    policy.check(&sys, process)?;
+    Ok(())
+}
+
+#[tauri::command]
+pub fn set_process_priority(
+    app: AppHandle,
+    pid: u32,
+    level: PriorityLevel,
+    policy: State<'_, PolicyState>,
+    system: State<'_, SharedSystem>,
+) -> Result<(), KillError> {
+    let name = audit::process_name(pid);
+    let result = checked(&policy, &system, pid).and_then(|()| {
+        apply_priority(pid, level).map_err(|e| KillError::Failed {
+            message: format!("Failed to change priority: {e}"),
+        })
+    });
+    let done = format!("Priority set to {level:?}");
+    audit::record_result(&app, "priority", pid, &name, &result, &done);
+    result
+}
+
+#[tauri::command]
+pub fn set_process_affinity(
+    app: AppHandle,
+    pid: u32,
+    cpus: Vec<u32>,
+    policy: State<'_, PolicyState>,
+    system: State<'_, SharedSystem>,
+) -> Result<(), KillError> {
+    let name = audit::process_name(pid);
+    let result = checked(&policy, &system, pid).and_then(|()| {
+        if cpus.is_empty() {
+            return Err(KillError::Failed {
+                message: "Choose at least one CPU".into(),
+            });
+        }
+        apply_affinity(pid, &cpus).map_err(|e| KillError::Failed {
+            message: format!("Failed to change CPU affinity: {e}"),
+        })
+    });
+    let done = format!("Restricted to CPUs {cpus:?}");
+    audit::record_result(&app, "affinity", pid, &name, &result, &done);
+    result
+}
@@ -0,0 +1,257 @@
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::PathBuf;
+use std::sync::Mutex;
+use sysinfo::{Pid, Process, System};
+use tauri::{AppHandle, Manager, State};
+
+use super::cgroups::in_cap_scope;
+use super::cleanup::is_elevated;
+
+const POLICY_FILE: &str = "process_protection.json";
+
+#[cfg(windows)]
+pub const ROOT_PIDS: [u32; 2] = [0, 4]; // System Idle Process, System
+#[cfg(not(windows))]
+pub const ROOT_PIDS: [u32; 1] = [1]; // init
+
+#[cfg(windows)]
+const CRITICAL_PROCESSES: &[&str] = &[
+    "System",
+    "Registry",
+    "Memory Compression",
+    "smss",
+    "csrss",
+    "wininit",
+    "winlogon",
+    "services",
+    "lsass",
+    "lsaiso",
+    "svchost",
+    "fontdrvhost",
+    "sihost",
+    "dwm",
+    "explorer",
+];
+
+#[cfg(not(windows))]
+const CRITICAL_PROCESSES: &[&str] = &[
+    "systemd",
+    "systemd-journald",
+    "systemd-logind",
+    "systemd-udevd",
+    "dbus-daemon",
+    "dbus-broker",
+    "polkitd",
+    "sshd",
+    "gdm",
+    "sddm",
+    "lightdm",
+    "Xorg",
+    "Xwayland",
+    "gnome-shell",
+    "kwin_wayland",
+    "kwin_x11",
+    "plasmashell",
+];
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(default)]
+pub struct ProtectionPolicy {
+    pub critical_processes: Vec<String>,
+    pub protect_other_users: bool,
+}
+
+impl Default for ProtectionPolicy {
+    fn default() -> Self {
+        ProtectionPolicy {
+            critical_processes: CRITICAL_PROCESSES.iter().map(|s| s.to_string()).collect(),
+            protect_other_users: true,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ProtectionRule {
+    Init,
+    OwnProcess,
+    Critical,
+    OtherUser,
+}
+
+#[derive(Debug, Serialize)]
+pub struct Protected {
+    pub rule: ProtectionRule,
+    pub message: String,
+}
+
+pub type PolicyState = Mutex<ProtectionPolicy>;
+
+fn normalize(name: &str) -> String {
+    let lower = name.to_lowercase();
+    lower.strip_suffix(".exe").unwrap_or(&lower).to_string()
+}
+
+// Linux truncates process names to 15 bytes (systemd-journald -> systemd-journal).
+fn name_matches(process_name: &str, entry: &str) -> bool {
+    let (name, entry) = (normalize(process_name), normalize(entry));
+    name == entry || (cfg!(target_os = "linux") && name.len() == 15 && entry.starts_with(&name))
+}
+
+// The app and its children: the webview runs in child processes, and ending
+// one takes the window down. Programs launched under a memory cap are
+// children too, but they're the user's and stay fair game, as does anything
+// they start.
+fn is_own_descendant(
+    pid: Pid,
+    own: Pid,
+    parent: impl Fn(Pid) -> Option<Pid>,
+    capped: impl Fn(Pid) -> bool,
+) -> bool {
+    let mut chain = Vec::new();
+    let mut current = Some(pid);
+    for _ in 0..64 {
+        match current {
+            Some(pid) if pid == own => return !chain.into_iter().any(capped),
+            Some(pid) => {
+                chain.push(pid);
+                current = parent(pid);
+            }
+            None => return false,
+        }
+    }
+    false
+}
+
+fn is_own_process(sys: &System, process: &Process) -> bool {
+    let own = match sysinfo::get_current_pid() {
+        Ok(pid) => pid,
+        Err(_) => return false,
+    };
+    is_own_descendant(
+        process.pid(),
+        own,
+        |pid| sys.process(pid).and_then(|p| p.parent()),
+        |pid| in_cap_scope(pid.as_u32()),
+    )
+}
+
+impl ProtectionPolicy {
+    pub fn check(&self, sys: &System, process: &Process) -> Result<(), Protected> {
+        let name = process.name().to_string_lossy().to_string();
+        let refuse = |rule, message: String| Err(Protected { rule, message });
+
+        if ROOT_PIDS.contains(&process.pid().as_u32()) {
+            return refuse(
+                ProtectionRule::Init,
+                format!("{name} is the root of the process tree and cannot be ended"),
+            );
+        }
+        if is_own_process(sys, process) {
+            return refuse(
+                ProtectionRule::OwnProcess,
+                format!("{name} is part of System Cleaner itself"),
+            );
+        }
+        if self
+            .critical_processes
+            .iter()
+            .any(|c| name_matches(&name, c))
+        {
+            return refuse(
+                ProtectionRule::Critical,
+                format!("{name} is critical to your session and is protected"),
+            );
+        }
+        if self.protect_other_users && !is_elevated() {
+            let own_user = sysinfo::get_current_pid()
+                .ok()
+                .and_then(|pid| sys.process(pid))
+                .and_then(|p| p.user_id());
+            if own_user.is_none() || process.user_id() != own_user {
+                return refuse(
+                    ProtectionRule::OtherUser,
+                    format!("{name} belongs to another user — run as administrator to end it"),
+                );
+            }
+        }
+        Ok(())
+    }
+
+    pub fn check_pid(&self, sys: &System, pid: u32) -> Result<(), Protected> {
+        match sys.process(Pid::from_u32(pid)) {
+            Some(process) => self.check(sys, process),
+            None => Ok(()),
+        }
+    }
+}
+
+fn policy_path(app: &AppHandle) -> Option<PathBuf> {
+    app.path()
+        .app_config_dir()
+        .ok()
+        .map(|dir| dir.join(POLICY_FILE))
+}
+
+pub fn load_policy(app: &AppHandle) -> ProtectionPolicy {
+    policy_path(app)
+        .and_then(|path| fs::read_to_string(path).ok())
+        .and_then(|text| serde_json::from_str(&text).ok())
+        .unwrap_or_default()
+}
+
+#[tauri::command]
+pub fn get_protection_policy(state: State<'_, PolicyState>) -> ProtectionPolicy {
+    state.lock().unwrap().clone()
+}
+
+#[tauri::command]
+pub fn set_protection_policy(
+    app: AppHandle,
+    state: State<'_, PolicyState>,
+    policy: Option<ProtectionPolicy>,
+) -> Result<ProtectionPolicy, String> {
+    let policy = policy.unwrap_or_default();
+    let path = policy_path(&app).ok_or("App config directory not available")?;
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir).map_err(|e| e.to_string())?;
+    }
+    let json = serde_json::to_string_pretty(&policy).map_err(|e| e.to_string())?;
+    fs::write(&path, json).map_err(|e| e.to_string())?;
+
+    *state.lock().unwrap() = policy.clone();
+    Ok(policy)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    #[test]
+    fn capped_launches_are_not_own_processes() {
+        let own = Pid::from_u32(100);
+        // 101 webview, 102 launched capped, 103 started by 102, 104 child of
+        // the webview, 200 unrelated
+        let parents: HashMap<u32, u32> = HashMap::from([
+            (100, 1),
+            (101, 100),
+            (102, 100),
+            (103, 102),
+            (104, 101),
+            (200, 1),
+        ]);
+        let parent = |pid: Pid| parents.get(&pid.as_u32()).map(|&p| Pid::from_u32(p));
+        let capped = |pid: Pid| matches!(pid.as_u32(), 102 | 103);
+
+        let own_process = |pid: u32| is_own_descendant(Pid::from_u32(pid), own, parent, capped);
+        assert!(own_process(100));
+        assert!(own_process(101));
+        assert!(own_process(104));
+        assert!(!own_process(102));
+        assert!(!own_process(103));
+        assert!(!own_process(200));
+        assert!(!own_process(1));
+    }
+}
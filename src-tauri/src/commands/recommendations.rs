@@ -1,6 +1,15 @@
 use serde::Serialize;
 use std::process::Command;
-use sysinfo::System;
+use tauri::State;
+
+use super::leaks::{find_leak_suspects, LeakSuspect};
+use super::memory::SharedSystem;
+use super::sampler::Sampler;
+
+// Percent of the last minute spent stalled on memory (Linux PSI)
+const PRESSURE_SOME_MEDIUM: f64 = 5.0;
+const PRESSURE_SOME_HIGH: f64 = 20.0;
+const PRESSURE_FULL_HIGH: f64 = 5.0;
 
 #[derive(Debug, Serialize)]
 pub struct Recommendation {
@@ -11,28 +20,92 @@ pub struct Recommendation {
     pub potential_savings: String,
     pub action_type: String,
     pub fixable: bool,
+    pub leak: Option<LeakSuspect>, // evidence for "memory_leak_*" entries
 }
 
 fn check_ram_usage(recs: &mut Vec<Recommendation>) {
-    let mut sys = System::new();
-    sys.refresh_memory();
+    let memory = super::memory::get_memory_info();
+    let total = memory.total_bytes;
+    let used = memory.used_bytes;
+    let usage_pct = memory.usage_percent;
+    // Pressure can fire below half usage; the estimate is left out then
+    let excess = used.saturating_sub(total / 2);
+    let freeable = if excess > 0 {
+        format!("{:.1} GB could be freed", excess as f64 / 1_073_741_824.0)
+    } else {
+        String::new()
+    };
 
-    let total = sys.total_memory();
-    let used = sys.used_memory();
-    let usage_pct = (used as f64 / total as f64) * 100.0;
+    // A full machine that isn't stalling is just using its RAM well, so
+    // pressure decides when it's available. Usage is the fallback elsewhere.
+    let Some(pressure) = memory.pressure else {
+        if usage_pct > 85.0 {
+            recs.push(Recommendation {
+                id: "high_ram".into(),
+                title: "Memory usage is very high".into(),
+                description: format!(
+                    "You're using {:.0}% of your RAM. Close unused programs or check the Processes tab for memory hogs.",
+                    usage_pct
+                ),
+                severity: "high".into(),
+                potential_savings: freeable,
+                action_type: "navigate_memory".into(),
+                fixable: false,
+                leak: None,
+            });
+        }
+        return;
+    };
 
-    if usage_pct > 85.0 {
+    let severity =
+        if pressure.full_avg60 >= PRESSURE_FULL_HIGH || pressure.some_avg60 >= PRESSURE_SOME_HIGH {
+            "high"
+        } else if pressure.some_avg60 >= PRESSURE_SOME_MEDIUM {
+            "medium"
+        } else {
+            return;
+        };
+    recs.push(Recommendation {
+        id: "high_ram".into(),
+        title: "The system is short of memory".into(),
+        description: format!(
+            "Programs spent {:.0}% of the last minute waiting for memory ({:.0}% of it with everything stalled), with {:.0}% of RAM in use. Close unused programs or check the Processes tab for memory hogs.",
+            pressure.some_avg60, pressure.full_avg60, usage_pct
+        ),
+        severity: severity.into(),
+        potential_savings: freeable,
+        action_type: "navigate_memory".into(),
+        fixable: false,
+        leak: None,
+    });
+}
+
+fn check_memory_leaks(recs: &mut Vec<Recommendation>, sampler: &Sampler, system: &SharedSystem) {
+    let config = sampler.config.lock().unwrap().clone();
+    // Same order as the process listings, System first, so they can't deadlock
+    let suspects = {
+        let sys = system.refreshed();
+        let watch = sampler.watch.lock().unwrap();
+        find_leak_suspects(&watch, &config, &sys)
+    };
+
+    for leak in suspects {
+        let grown = leak.current_bytes.saturating_sub(leak.start_bytes);
         recs.push(Recommendation {
-            id: "high_ram".into(),
-            title: "Memory usage is very high".into(),
+            id: format!("memory_leak_{}", leak.pid),
+            title: format!("{} may be leaking memory", leak.name),
             description: format!(
-                "You're using {:.0}% of your RAM. Close unused programs or check the Processes tab for memory hogs.",
-                usage_pct
+                "It grew steadily from {:.0} MB to {:.0} MB over the last {:.1} hours (about {:.0} MB per hour). Restarting it will give the memory back.",
+                leak.start_bytes as f64 / 1_048_576.0,
+                leak.current_bytes as f64 / 1_048_576.0,
+                leak.duration_secs as f64 / 3600.0,
+                leak.slope_mb_per_hour,
             ),
-            severity: "high".into(),
-            potential_savings: format!("{:.1} GB could be freed", (used as f64 - total as f64 * 0.5) / 1_073_741_824.0),
+            severity: "medium".into(),
+            potential_savings: format!("{:.0} MB", grown as f64 / 1_048_576.0),
             action_type: "navigate_memory".into(),
             fixable: false,
+            leak: Some(leak),
         });
     }
 }
@@ -56,6 +129,7 @@ fn check_startup_count(recs: &mut Vec<Recommendation>) {
             potential_savings: "Faster boot time".into(),
             action_type: "navigate_startup".into(),
             fixable: false,
+            leak: None,
         });
     }
 }
@@ -98,6 +172,7 @@ fn check_hyper_v_vms(recs: &mut Vec<Recommendation>) {
                         potential_savings: format!("{:.1} GB", (assigned - startup) as f64 / 1_073_741_824.0),
                         action_type: "info".into(),
                         fixable: false,
+                        leak: None,
                     });
                 }
             }
@@ -122,6 +197,7 @@ fn check_disk_space(recs: &mut Vec<Recommendation>) {
                 potential_savings: "Varies".into(),
                 action_type: "navigate_disk".into(),
                 fixable: false,
+                leak: None,
             });
         } else if free_pct < 20.0 {
             recs.push(Recommendation {
@@ -137,6 +213,7 @@ fn check_disk_space(recs: &mut Vec<Recommendation>) {
                 potential_savings: "Varies".into(),
                 action_type: "navigate_disk".into(),
                 fixable: false,
+                leak: None,
             });
         }
     }
@@ -159,15 +236,20 @@ fn check_cleanable_items(recs: &mut Vec<Recommendation>) {
             ),
             action_type: "navigate_disk".into(),
             fixable: false,
+            leak: None,
         });
     }
 }
 
 #[tauri::command]
-pub fn get_recommendations() -> Vec<Recommendation> {
+pub fn get_recommendations(
+    sampler: State<'_, Sampler>,
+    system: State<'_, SharedSystem>,
+) -> Vec<Recommendation> {
     let mut recs = Vec::new();
 
     check_ram_usage(&mut recs);
+    check_memory_leaks(&mut recs, &sampler, &system);
     check_disk_space(&mut recs);
     check_startup_count(&mut recs);
     check_hyper_v_vms(&mut recs);
@@ -0,0 +1,595 @@
+use serde::{Deserialize, Serialize};
+use std::cmp::Reverse;
+use std::collections::{HashMap, VecDeque};
+use std::fs;
+use std::path::PathBuf;
+use std::sync::Mutex;
+use std::thread;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use sysinfo::{ProcessStatus, ProcessesToUpdate, System};
+use tauri::{AppHandle, Manager, State};
+
+const CONFIG_FILE: &str = "sampler.json";
+const HISTORY_FILE: &str = "memory_history.json";
+const FINE_RETENTION_SECS: u64 = 60 * 60;
+const COARSE_RESOLUTION_SECS: u64 = 60;
+const COARSE_RETENTION_SECS: u64 = 24 * 60 * 60;
+const MAX_POINTS: u64 = 360;
+const BUSY_CPU_PERCENT: f32 = 90.0;
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(default)]
+pub struct SamplerConfig {
+    pub interval_secs: u64,
+    pub top_processes: usize,
+    pub persist: bool,
+    pub leak_window_secs: u64,
+    pub leak_threshold_mb_per_hour: f64,
+}
+
+impl Default for SamplerConfig {
+    fn default() -> Self {
+        SamplerConfig {
+            interval_secs: 5,
+            top_processes: 10,
+            persist: false,
+            leak_window_secs: 4 * 60 * 60,
+            leak_threshold_mb_per_hour: 50.0,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ProcessSample {
+    pub pid: u32,
+    pub name: String,
+    pub memory_bytes: u64,
+    pub cpu_percent: f32,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct MemorySample {
+    pub timestamp: u64,
+    pub total_bytes: u64,
+    pub used_bytes: u64,
+    pub swap_total_bytes: u64,
+    pub swap_used_bytes: u64,
+    pub processes: Vec<ProcessSample>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct MemoryHistory {
+    pub resolution_secs: u64,
+    pub samples: Vec<MemorySample>,
+}
+
+// Two rings: every sample for the last hour, and one-minute averages for the
+// last day. Longer ranges are served from the coarse ring.
+#[derive(Debug, Serialize, Deserialize, Default)]
+pub struct History {
+    fine: VecDeque<MemorySample>,
+    coarse: VecDeque<MemorySample>,
+}
+
+// RSS of one process over the leak window, one point per minute. Kept for
+// every process, not just the top N in the history, so a slow leak in a small
+// process is still seen.
+#[derive(Debug)]
+pub struct RssSeries {
+    pub name: String,
+    pub points: VecDeque<(u64, u64)>, // (timestamp, bytes)
+}
+
+impl RssSeries {
+    // A later sample in the same minute replaces the earlier one.
+    fn record(&mut self, now: u64, bytes: u64, window_secs: u64) {
+        let minute = now / COARSE_RESOLUTION_SECS;
+        match self.points.back_mut() {
+            Some(last) if last.0 / COARSE_RESOLUTION_SECS == minute => *last = (now, bytes),
+            _ => self.points.push_back((now, bytes)),
+        }
+        while self
+            .points
+            .front()
+            .is_some_and(|&(t, _)| t + window_secs < now)
+        {
+            self.points.pop_front();
+        }
+    }
+}
+
+// Per-process state that only means something across consecutive samples.
+// Keyed by (pid, start time) so a reused pid starts over.
+#[derive(Debug, Default)]
+pub struct ProcessWatch {
+    updated: u64,
+    stuck_since: HashMap<(u32, u64), u64>,
+    busy_since: HashMap<(u32, u64), (u64, f32, u32)>,
+    rss: HashMap<(u32, u64), RssSeries>,
+}
+
+pub struct Sampler {
+    pub config: Mutex<SamplerConfig>,
+    pub history: Mutex<History>,
+    pub watch: Mutex<ProcessWatch>,
+}
+
+pub fn now_secs() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+fn take_sample(sys: &mut System, top_processes: usize) -> MemorySample {
+    sys.refresh_memory();
+    sys.refresh_processes(ProcessesToUpdate::All, true);
+
+    let mut processes: Vec<ProcessSample> = sys
+        .processes()
+        .values()
+        .filter(|p| p.thread_kind().is_none())
+        .map(|p| ProcessSample {
+            pid: p.pid().as_u32(),
+            name: p.name().to_string_lossy().to_string(),
+            memory_bytes: p.memory(),
+            cpu_percent: p.cpu_usage(),
+        })
+        .collect();
+    processes.sort_by_key(|p| Reverse(p.memory_bytes));
+    processes.truncate(top_processes);
+
+    MemorySample {
+        timestamp: now_secs(),
+        total_bytes: sys.total_memory(),
+        used_bytes: sys.used_memory(),
+        swap_total_bytes: sys.total_swap(),
+        swap_used_bytes: sys.used_swap(),
+        processes,
+    }
+}
+
+fn average(bucket: &[MemorySample], timestamp: u64, top_processes: usize) -> MemorySample {
+    let n = bucket.len().max(1) as u64;
+    let mean = |f: fn(&MemorySample) -> u64| bucket.iter().map(f).sum::<u64>() / n;
+
+    // A process that dropped out of the top N for part of the bucket is
+    // averaged over the samples it appeared in.
+    let mut by_pid: HashMap<u32, (String, u64, f32, u32)> = HashMap::new();
+    for p in bucket.iter().flat_map(|s| &s.processes) {
+        let entry = by_pid.entry(p.pid).or_insert((p.name.clone(), 0, 0.0, 0));
+        entry.1 += p.memory_bytes;
+        entry.2 += p.cpu_percent;
+        entry.3 += 1;
+    }
+    let mut processes: Vec<ProcessSample> = by_pid
+        .into_iter()
+        .map(|(pid, (name, mem, cpu, count))| ProcessSample {
+            pid,
+            name,
+            memory_bytes: mem / count as u64,
+            cpu_percent: cpu / count as f32,
+        })
+        .collect();
+    processes.sort_by_key(|p| Reverse(p.memory_bytes));
+    processes.truncate(top_processes);
+
+    MemorySample {
+        timestamp,
+        total_bytes: mean(|s| s.total_bytes),
+        used_bytes: mean(|s| s.used_bytes),
+        swap_total_bytes: mean(|s| s.swap_total_bytes),
+        swap_used_bytes: mean(|s| s.swap_used_bytes),
+        processes,
+    }
+}
+
+fn downsample(
+    samples: &[MemorySample],
+    resolution_secs: u64,
+    top_processes: usize,
+) -> Vec<MemorySample> {
+    let resolution = resolution_secs.max(1);
+    samples
+        .chunk_by(|a, b| a.timestamp / resolution == b.timestamp / resolution)
+        .map(|bucket| {
+            let start = bucket[0].timestamp / resolution * resolution;
+            average(bucket, start, top_processes)
+        })
+        .collect()
+}
+
+impl History {
+    fn push(&mut self, sample: MemorySample, config: &SamplerConfig) -> bool {
+        let minute = sample.timestamp / COARSE_RESOLUTION_SECS;
+        let rolled_over = self
+            .fine
+            .back()
+            .is_some_and(|last| last.timestamp / COARSE_RESOLUTION_SECS != minute);
+
+        if rolled_over {
+            let last_minute = self.fine.back().unwrap().timestamp / COARSE_RESOLUTION_SECS;
+            let bucket: Vec<MemorySample> = self
+                .fine
+                .iter()
+                .filter(|s| s.timestamp / COARSE_RESOLUTION_SECS == last_minute)
+                .cloned()
+                .collect();
+            self.coarse.push_back(average(
+                &bucket,
+                last_minute * COARSE_RESOLUTION_SECS,
+                config.top_processes,
+            ));
+        }
+
+        self.fine.push_back(sample);
+
+        let now = now_secs();
+        while self
+            .fine
+            .front()
+            .is_some_and(|s| s.timestamp + FINE_RETENTION_SECS < now)
+        {
+            self.fine.pop_front();
+        }
+        while self
+            .coarse
+            .front()
+            .is_some_and(|s| s.timestamp + COARSE_RETENTION_SECS < now)
+        {
+            self.coarse.pop_front();
+        }
+
+        rolled_over
+    }
+
+    pub fn query(
+        &self,
+        range_secs: u64,
+        resolution_secs: Option<u64>,
+        config: &SamplerConfig,
+    ) -> MemoryHistory {
+        let since = now_secs().saturating_sub(range_secs);
+        let resolution = resolution_secs.unwrap_or(range_secs / MAX_POINTS).max(1);
+
+        let (ring, ring_resolution) =
+            if range_secs <= FINE_RETENTION_SECS && resolution < COARSE_RESOLUTION_SECS {
+                (&self.fine, config.interval_secs)
+            } else {
+                (&self.coarse, COARSE_RESOLUTION_SECS)
+            };
+        let samples: Vec<MemorySample> = ring
+            .iter()
+            .filter(|s| s.timestamp >= since)
+            .cloned()
+            .collect();
+
+        if resolution <= ring_resolution {
+            return MemoryHistory {
+                resolution_secs: ring_resolution,
+                samples,
+            };
+        }
+        MemoryHistory {
+            resolution_secs: resolution,
+            samples: downsample(&samples, resolution, config.top_processes),
+        }
+    }
+}
+
+impl ProcessWatch {
+    fn update(&mut self, sys: &System, now: u64, leak_window_secs: u64) {
+        let mut stuck_since = HashMap::new();
+        let mut busy_since = HashMap::new();
+        let mut rss = HashMap::new();
+
+        for p in sys
+            .processes()
+            .values()
+            .filter(|p| p.thread_kind().is_none())
+        {
+            let key = (p.pid().as_u32(), p.start_time());
+            if p.status() == ProcessStatus::UninterruptibleDiskSleep {
+                let since = self.stuck_since.get(&key).copied().unwrap_or(now);
+                stuck_since.insert(key, since);
+            }
+            if p.cpu_usage() >= BUSY_CPU_PERCENT {
+                let (since, sum, n) = self.busy_since.get(&key).copied().unwrap_or((now, 0.0, 0));
+                busy_since.insert(key, (since, sum + p.cpu_usage(), n + 1));
+            }
+
+            let mut series = self.rss.remove(&key).unwrap_or_else(|| RssSeries {
+                name: p.name().to_string_lossy().to_string(),
+                points: VecDeque::new(),
+            });
+            series.record(now, p.memory(), leak_window_secs);
+            rss.insert(key, series);
+        }
+
+        self.updated = now;
+        self.stuck_since = stuck_since;
+        self.busy_since = busy_since;
+        self.rss = rss;
+    }
+
+    // RSS series of every running process, keyed by (pid, start time).
+    pub fn rss_series(&self) -> &HashMap<(u32, u64), RssSeries> {
+        &self.rss
+    }
+
+    // Seconds the process has been in uninterruptible sleep (D state) in
+    // every sample.
+    pub fn stuck_secs(&self, pid: u32, start_time: u64) -> Option<u64> {
+        self.stuck_since
+            .get(&(pid, start_time))
+            .map(|since| self.updated - since)
+    }
+
+    // Seconds the process has used 90% of a core or more in every sample,
+    // with its average CPU over that time. cpu_usage() is per core, so a
+    // thread spinning flat out reads close to 100, a little under at times.
+    pub fn busy_secs(&self, pid: u32, start_time: u64) -> Option<(u64, f32)> {
+        self.busy_since
+            .get(&(pid, start_time))
+            .map(|&(since, sum, n)| (self.updated - since, sum / n as f32))
+    }
+}
+
+fn config_path(app: &AppHandle) -> Option<PathBuf> {
+    app.path()
+        .app_config_dir()
+        .ok()
+        .map(|dir| dir.join(CONFIG_FILE))
+}
+
+fn history_path(app: &AppHandle) -> Option<PathBuf> {
+    app.path()
+        .app_data_dir()
+        .ok()
+        .map(|dir| dir.join(HISTORY_FILE))
+}
+
+fn save_json<T: Serialize>(path: Option<PathBuf>, value: &T) -> Result<(), String> {
+    let path = path.ok_or("App directory not available")?;
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir).map_err(|e| e.to_string())?;
+    }
+    let json = serde_json::to_string(value).map_err(|e| e.to_string())?;
+    fs::write(&path, json).map_err(|e| e.to_string())
+}
+
+pub fn load_sampler(app: &AppHandle) -> Sampler {
+    let config: SamplerConfig = config_path(app)
+        .and_then(|path| fs::read_to_string(path).ok())
+        .and_then(|text| serde_json::from_str(&text).ok())
+        .unwrap_or_default();
+    let history = if config.persist {
+        history_path(app)
+            .and_then(|path| fs::read_to_string(path).ok())
+            .and_then(|text| serde_json::from_str(&text).ok())
+            .unwrap_or_default()
+    } else {
+        History::default()
+    };
+
+    Sampler {
+        config: Mutex::new(config),
+        history: Mutex::new(history),
+        watch: Mutex::new(ProcessWatch::default()),
+    }
+}
+
+pub fn start_sampler(app: AppHandle) {
+    thread::spawn(move || {
+        let mut sys = System::new();
+        loop {
+            let sampler = app.state::<Sampler>();
+            let config = sampler.config.lock().unwrap().clone();
+
+            let sample = take_sample(&mut sys, config.top_processes);
+            sampler
+                .watch
+                .lock()
+                .unwrap()
+                .update(&sys, sample.timestamp, config.leak_window_secs);
+            let rolled_over = sampler.history.lock().unwrap().push(sample, &config);
+
+            // Writing once a minute keeps the file current without churning the disk.
+            if rolled_over && config.persist {
+                let history = sampler.history.lock().unwrap();
+                let _ = save_json(history_path(&app), &*history);
+            }
+
+            thread::sleep(Duration::from_secs(config.interval_secs.clamp(1, 3600)));
+        }
+    });
+}
+
+#[tauri::command]
+pub fn get_memory_history(
+    state: State<'_, Sampler>,
+    range_secs: u64,
+    resolution_secs: Option<u64>,
+) -> MemoryHistory {
+    let config = state.config.lock().unwrap().clone();
+    state
+        .history
+        .lock()
+        .unwrap()
+        .query(range_secs, resolution_secs, &config)
+}
+
+#[tauri::command]
+pub fn get_sampler_config(state: State<'_, Sampler>) -> SamplerConfig {
+    state.config.lock().unwrap().clone()
+}
+
+#[tauri::command]
+pub fn set_sampler_config(
+    app: AppHandle,
+    state: State<'_, Sampler>,
+    config: Option<SamplerConfig>,
+) -> Result<SamplerConfig, String> {
+    let mut config = config.unwrap_or_default();
+    config.interval_secs = config.interval_secs.clamp(1, 3600);
+    config.leak_window_secs = config
+        .leak_window_secs
+        .clamp(15 * 60, COARSE_RETENTION_SECS);
+
+    save_json(config_path(&app), &config)?;
+    if !config.persist {
+        if let Some(path) = history_path(&app) {
+            let _ = fs::remove_file(path);
+        }
+    }
+
+    *state.config.lock().unwrap() = config.clone();
+    Ok(config)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample(timestamp: u64, used_bytes: u64, processes: &[(u32, u64)]) -> MemorySample {
+        MemorySample {
+            timestamp,
+            total_bytes: 1000,
+            used_bytes,
+            swap_total_bytes: 0,
+            swap_used_bytes: 0,
+            processes: processes
+                .iter()
+                .map(|&(pid, memory_bytes)| ProcessSample {
+                    pid,
+                    name: format!("p{pid}"),
+                    memory_bytes,
+                    cpu_percent: 10.0,
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn averages_processes_over_the_samples_they_appear_in() {
+        let bucket = [
+            sample(120, 100, &[(1, 300), (2, 50)]),
+            sample(125, 200, &[(1, 500), (3, 40)]),
+            sample(130, 300, &[(1, 400), (2, 70)]),
+        ];
+        let avg = average(&bucket, 120, 2);
+        assert_eq!(avg.timestamp, 120);
+        assert_eq!(avg.used_bytes, 200);
+        let processes: Vec<(u32, u64)> = avg
+            .processes
+            .iter()
+            .map(|p| (p.pid, p.memory_bytes))
+            .collect();
+        // pid 3's 40 would outrank nothing; only the top 2 are kept
+        assert_eq!(processes, [(1, 400), (2, 60)]);
+    }
+
+    #[test]
+    fn downsamples_into_aligned_buckets() {
+        let samples: Vec<MemorySample> = (0..24).map(|i| sample(i * 5, i * 5, &[])).collect();
+        let buckets = downsample(&samples, 60, 10);
+        let summary: Vec<(u64, u64)> = buckets
+            .iter()
+            .map(|s| (s.timestamp, s.used_bytes))
+            .collect();
+        // mean of 0, 5, ..., 55 and of 60, ..., 115, rounded down
+        assert_eq!(summary, [(0, 27), (60, 87)]);
+    }
+
+    #[test]
+    fn expired_fine_samples_survive_as_minute_averages() {
+        let config = SamplerConfig::default();
+        let now = now_secs();
+        let old = (now - FINE_RETENTION_SECS - 120) / 60 * 60;
+        // As if sampling stopped for over an hour, say across a suspend
+        let mut history = History::default();
+        history.fine.push_back(sample(old, 100, &[]));
+        history.fine.push_back(sample(old + 30, 300, &[]));
+        assert!(history.push(sample(now, 500, &[]), &config));
+
+        let fine: Vec<u64> = history.fine.iter().map(|s| s.timestamp).collect();
+        assert_eq!(fine, [now]);
+        let coarse: Vec<(u64, u64)> = history
+            .coarse
+            .iter()
+            .map(|s| (s.timestamp, s.used_bytes))
+            .collect();
+        assert_eq!(coarse, [(old, 200)]);
+    }
+
+    #[test]
+    fn queries_pick_the_ring_and_bucket_size() {
+        let config = SamplerConfig::default();
+        let now = now_secs();
+        let start = (now - 50 * 60) / 60 * 60;
+        let mut history = History::default();
+        let mut t = start;
+        while t <= now {
+            history.push(sample(t, t, &[]), &config);
+            t += config.interval_secs;
+        }
+        assert_eq!(history.fine.front().map(|s| s.timestamp), Some(start));
+        // one average per completed minute
+        assert_eq!(history.coarse.front().map(|s| s.timestamp), Some(start));
+        assert!(history.coarse.len() >= 49);
+        assert!(history
+            .coarse
+            .iter()
+            .all(|s| s.timestamp % 60 == 0 && s.used_bytes == s.timestamp + 27));
+
+        // Short ranges at fine resolution come straight from the fine ring
+        let recent = history.query(30 * 60, None, &config);
+        assert_eq!(recent.resolution_secs, config.interval_secs);
+        assert!(recent.samples.iter().all(|s| s.timestamp + 30 * 60 >= now));
+        assert!(recent.samples.len() >= 355);
+
+        // Coarser requests within the hour are averaged from the fine ring
+        let hour = history.query(60 * 60, Some(30), &config);
+        assert_eq!(hour.resolution_secs, 30);
+        assert_eq!(hour.samples.first().map(|s| s.timestamp), Some(start));
+        assert!(hour
+            .samples
+            .iter()
+            .all(|s| s.timestamp % 30 == 0 && s.used_bytes >= s.timestamp));
+
+        // Past the hour the coarse ring takes over, bucketed to MAX_POINTS
+        let two_hours = history.query(2 * 60 * 60, None, &config);
+        assert_eq!(two_hours.resolution_secs, COARSE_RESOLUTION_SECS);
+        assert_eq!(two_hours.samples.len(), history.coarse.len());
+        let day = history.query(COARSE_RETENTION_SECS, None, &config);
+        assert_eq!(day.resolution_secs, COARSE_RETENTION_SECS / MAX_POINTS);
+        assert!(day.samples.len() as u64 <= MAX_POINTS);
+        assert!(day
+            .samples
+            .iter()
+            .all(|s| s.timestamp % day.resolution_secs == 0));
+    }
+
+    #[test]
+    fn rss_series_keeps_one_point_a_minute_over_the_window() {
+        let mut series = RssSeries {
+            name: "leaky".into(),
+            points: VecDeque::new(),
+        };
+        let window = 10 * 60;
+        // every 5 s for 15 minutes, growing 1 KB per sample
+        for i in 0..180u64 {
+            series.record(6000 + i * 5, 1024 * i, window);
+        }
+        let last = 6000 + 179 * 5;
+
+        // the last reading of each minute, within the window
+        assert!(series.points.iter().all(|&(t, _)| t + window >= last));
+        assert_eq!(series.points.back(), Some(&(last, 1024 * 179)));
+        let minutes: Vec<u64> = series.points.iter().map(|&(t, _)| t / 60).collect();
+        let mut unique = minutes.clone();
+        unique.dedup();
+        assert_eq!(minutes, unique);
+        assert_eq!(series.points.len(), 11);
+    }
+}
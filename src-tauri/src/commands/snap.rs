@@ -0,0 +1,163 @@
+use serde::Serialize;
+use std::fs;
+use std::path::Path;
+
+use super::cleanup::{run_privileged, CleanupResult};
+use super::disk::CleanableItem;
+
+const SNAP_MOUNT_DIRS: [&str; 2] = ["/snap", "/var/lib/snapd/snap"];
+const SNAP_BLOB_DIR: &str = "/var/lib/snapd/snaps";
+
+#[derive(Debug, Serialize, Clone)]
+pub struct SnapRevision {
+    pub name: String,
+    pub revision: String,
+    pub size_bytes: u64,
+    pub current: bool,
+}
+
+fn snap_mount_dir() -> Option<&'static Path> {
+    SNAP_MOUNT_DIRS.iter().map(Path::new).find(|p| p.is_dir())
+}
+
+// <mount>/<name>/<revision> with <mount>/<name>/current -> <revision>;
+// every other revision is disabled and kept only for rollback.
+fn list_revisions() -> Vec<SnapRevision> {
+    let mut revisions = Vec::new();
+    let mount = match snap_mount_dir() {
+        Some(m) => m,
+        None => return revisions,
+    };
+    let snaps = match fs::read_dir(mount) {
+        Ok(s) => s,
+        Err(_) => return revisions,
+    };
+
+    for snap in snaps.flatten() {
+        let name = snap.file_name().to_string_lossy().to_string();
+        if name == "bin" || !snap.path().is_dir() {
+            continue;
+        }
+        // Without the link there is no telling which revision is active
+        let current = match fs::read_link(snap.path().join("current")) {
+            Ok(t) => t.to_string_lossy().to_string(),
+            Err(_) => continue,
+        };
+
+        let entries = match fs::read_dir(snap.path()) {
+            Ok(e) => e,
+            Err(_) => continue,
+        };
+        for entry in entries.flatten() {
+            let revision = entry.file_name().to_string_lossy().to_string();
+            // local installs use x1, x2, ...
+            let digits = revision.strip_prefix('x').unwrap_or(&revision);
+            if digits.is_empty() || !digits.bytes().all(|b| b.is_ascii_digit()) {
+                continue;
+            }
+            let blob = Path::new(SNAP_BLOB_DIR).join(format!("{name}_{revision}.snap"));
+            revisions.push(SnapRevision {
+                name: name.clone(),
+                size_bytes: blob.metadata().map(|m| m.len()).unwrap_or(0),
+                current: revision == current,
+                revision,
+            });
+        }
+    }
+
+    revisions.sort_by(|a, b| {
+        a.name
+            .cmp(&b.name)
+            .then_with(|| a.revision.cmp(&b.revision))
+    });
+    revisions
+}
+
+fn remove_revision(name: &str, revision: &str) -> (bool, String) {
+    run_privileged("snap", &["remove", name, &format!("--revision={revision}")])
+}
+
+pub fn scan_snap() -> Vec<CleanableItem> {
+    let mut items = Vec::new();
+    let disabled: Vec<SnapRevision> = list_revisions()
+        .into_iter()
+        .filter(|r| !r.current)
+        .collect();
+    let size: u64 = disabled.iter().map(|r| r.size_bytes).sum();
+
+    if size > 0 {
+        items.push(CleanableItem {
+            id: "snap_disabled".into(),
+            name: "Old Snap Revisions".into(),
+            description: format!(
+                "{} disabled revisions kept for rollback — removed with snap remove --revision",
+                disabled.len()
+            ),
+            size_bytes: size,
+            path: SNAP_BLOB_DIR.into(),
+            risk: "moderate".into(),
+            category: "Applications".into(),
+            requires_elevation: true,
+        });
+    }
+
+    items
+}
+
+pub fn remove_disabled_revisions() -> (u64, String) {
+    let mut freed = 0u64;
+    let mut errors = 0u32;
+    let mut messages = Vec::new();
+
+    for rev in list_revisions().into_iter().filter(|r| !r.current) {
+        let (success, output) = remove_revision(&rev.name, &rev.revision);
+        if success {
+            freed += rev.size_bytes;
+        } else {
+            errors += 1;
+        }
+        messages.push(format!("{} ({}): {output}", rev.name, rev.revision));
+    }
+
+    let summary = if errors > 0 {
+        format!("Cleaned with {errors} revisions skipped")
+    } else {
+        "Cleaned successfully".into()
+    };
+    messages.insert(0, summary);
+    (freed, messages.join("\n"))
+}
+
+#[tauri::command]
+pub fn get_snap_revisions() -> Vec<SnapRevision> {
+    list_revisions()
+}
+
+#[tauri::command(async)]
+pub fn remove_snap_revision(name: String, revision: String) -> CleanupResult {
+    let rev = list_revisions()
+        .into_iter()
+        .find(|r| r.name == name && r.revision == revision);
+    let id = format!("{name}_{revision}");
+
+    let (success, message, size) = match rev {
+        None => (false, "Revision not found".into(), 0),
+        Some(r) if r.current => (
+            false,
+            "This is the active revision — remove the snap itself instead".into(),
+            0,
+        ),
+        Some(r) => {
+            let (success, message) = remove_revision(&name, &revision);
+            (success, message, r.size_bytes)
+        }
+    };
+
+    CleanupResult {
+        name: format!("{name} revision {revision}"),
+        id,
+        success,
+        bytes_freed: if success { size } else { 0 },
+        message,
+    }
+}
@@ -1,7 +1,9 @@
 use serde::Serialize;
 use std::env;
 use std::fs;
+#[cfg(windows)]
 use winreg::enums::*;
+#[cfg(windows)]
 use winreg::RegKey;
 
 #[derive(Debug, Serialize, Clone)]
@@ -46,6 +48,7 @@ fn known_safe_to_disable() -> Vec<&'static str> {
     ]
 }
 
+#[cfg(windows)]
 fn estimate_impact(command: &str) -> String {
     let lower = command.to_lowercase();
     if lower.contains("steam")
@@ -68,6 +71,7 @@ fn estimate_impact(command: &str) -> String {
     }
 }
 
+#[cfg(windows)]
 fn scan_registry_run(hkey: &RegKey, source: &str, items: &mut Vec<StartupItem>) {
     let safe_list = known_safe_to_disable();
 
@@ -138,17 +142,70 @@ fn scan_startup_folder(items: &mut Vec<StartupItem>) {
 pub fn get_startup_items() -> Vec<StartupItem> {
     let mut items = Vec::new();
 
-    let hkcu = RegKey::predef(HKEY_CURRENT_USER);
-    scan_registry_run(&hkcu, "Current User", &mut items);
+    #[cfg(windows)]
+    {
+        let hkcu = RegKey::predef(HKEY_CURRENT_USER);
+        scan_registry_run(&hkcu, "Current User", &mut items);
 
-    let hklm = RegKey::predef(HKEY_LOCAL_MACHINE);
-    scan_registry_run(&hklm, "All Users", &mut items);
+        let hklm = RegKey::predef(HKEY_LOCAL_MACHINE);
+        scan_registry_run(&hklm, "All Users", &mut items);
+    }
 
     scan_startup_folder(&mut items);
 
     items
 }
 
+#[cfg(windows)]
+fn toggle_registry_item(scope: &str, item_name: &str, enable: bool) -> Result<String, String> {
+    let hkey = if scope.starts_with("Current") {
+        RegKey::predef(HKEY_CURRENT_USER)
+    } else {
+        RegKey::predef(HKEY_LOCAL_MACHINE)
+    };
+
+    if enable {
+        if let Ok(approved_key) = hkey.open_subkey_with_flags(
+            "Software\\Microsoft\\Windows\\CurrentVersion\\Explorer\\StartupApproved\\Run",
+            KEY_SET_VALUE,
+        ) {
+            let enabled_bytes: [u8; 12] = [0x02, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0];
+            approved_key
+                .set_raw_value(
+                    item_name,
+                    &winreg::RegValue {
+                        bytes: enabled_bytes.to_vec(),
+                        vtype: REG_BINARY,
+                    },
+                )
+                .map_err(|e| e.to_string())?;
+        }
+        Ok(format!("{item_name} enabled"))
+    } else {
+        if let Ok(approved_key) = hkey.open_subkey_with_flags(
+            "Software\\Microsoft\\Windows\\CurrentVersion\\Explorer\\StartupApproved\\Run",
+            KEY_SET_VALUE,
+        ) {
+            let disabled_bytes: [u8; 12] = [0x03, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0];
+            approved_key
+                .set_raw_value(
+                    item_name,
+                    &winreg::RegValue {
+                        bytes: disabled_bytes.to_vec(),
+                        vtype: REG_BINARY,
+                    },
+                )
+                .map_err(|e| e.to_string())?;
+        }
+        Ok(format!("{item_name} disabled"))
+    }
+}
+
+#[cfg(not(windows))]
+fn toggle_registry_item(_scope: &str, _item_name: &str, _enable: bool) -> Result<String, String> {
+    Err("Registry startup items are only available on Windows".into())
+}
+
 #[tauri::command]
 pub fn toggle_startup_item(id: String, enable: bool) -> Result<String, String> {
     let parts: Vec<&str> = id.splitn(3, '_').collect();
@@ -160,50 +217,7 @@ pub fn toggle_startup_item(id: String, enable: bool) -> Result<String, String> {
     let item_name = parts[2..].join("_");
 
     match source_type {
-        "reg" => {
-            let scope = parts[1];
-            let hkey = if scope.starts_with("Current") {
-                RegKey::predef(HKEY_CURRENT_USER)
-            } else {
-                RegKey::predef(HKEY_LOCAL_MACHINE)
-            };
-
-            if enable {
-                if let Ok(approved_key) = hkey.open_subkey_with_flags(
-                    "Software\\Microsoft\\Windows\\CurrentVersion\\Explorer\\StartupApproved\\Run",
-                    KEY_SET_VALUE,
-                ) {
-                    let enabled_bytes: [u8; 12] = [0x02, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0];
-                    approved_key
-                        .set_raw_value(
-                            &item_name,
-                            &winreg::RegValue {
-                                bytes: enabled_bytes.to_vec(),
-                                vtype: REG_BINARY,
-                            },
-                        )
-                        .map_err(|e| e.to_string())?;
-                }
-                Ok(format!("{item_name} enabled"))
-            } else {
-                if let Ok(approved_key) = hkey.open_subkey_with_flags(
-                    "Software\\Microsoft\\Windows\\CurrentVersion\\Explorer\\StartupApproved\\Run",
-                    KEY_SET_VALUE,
-                ) {
-                    let disabled_bytes: [u8; 12] = [0x03, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0];
-                    approved_key
-                        .set_raw_value(
-                            &item_name,
-                            &winreg::RegValue {
-                                bytes: disabled_bytes.to_vec(),
-                                vtype: REG_BINARY,
-                            },
-                        )
-                        .map_err(|e| e.to_string())?;
-                }
-                Ok(format!("{item_name} disabled"))
-            }
-        }
+        "reg" => toggle_registry_item(parts[1], &item_name, enable),
         "folder" => {
             let appdata = env::var("APPDATA").map_err(|e| e.to_string())?;
             let startup_path =
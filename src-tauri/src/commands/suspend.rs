@@ -0,0 +1,254 @@
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::PathBuf;
+use std::sync::Mutex;
+use sysinfo::{Pid, Process, ProcessesToUpdate, System};
+use tauri::{AppHandle, Manager, State};
+
+use super::audit;
+use super::memory::{KillError, ProcessInfo, SharedSystem};
+use super::protection::{PolicyState, ProtectionPolicy};
+use super::sampler::now_secs;
+
+#[cfg(unix)]
+use sysinfo::Signal;
+
+const SUSPENDED_FILE: &str = "suspended_processes.json";
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct SuspendedProcess {
+    pub pid: u32,
+    pub name: String,
+    pub start_time: u64,
+    pub suspended_at: u64,
+}
+
+// Everything the app has paused. The list is kept on disk so that processes
+// left frozen by a crash are resumed on the next start, and cleared on exit.
+pub struct Suspended {
+    path: Option<PathBuf>,
+    processes: Mutex<Vec<SuspendedProcess>>,
+}
+
+#[cfg(unix)]
+fn set_suspended(process: &Process, suspend: bool) -> bool {
+    let signal = if suspend {
+        Signal::Stop
+    } else {
+        Signal::Continue
+    };
+    process.kill_with(signal).unwrap_or(false)
+}
+
+// Windows has no process-wide stop signal, so every thread is suspended in
+// turn. Suspension is counted per thread; each suspend needs one resume.
+#[cfg(windows)]
+fn set_suspended(process: &Process, suspend: bool) -> bool {
+    use windows::Win32::Foundation::CloseHandle;
+    use windows::Win32::System::Diagnostics::ToolHelp::{
+        CreateToolhelp32Snapshot, Thread32First, Thread32Next, TH32CS_SNAPTHREAD, THREADENTRY32,
+    };
+    use windows::Win32::System::Threading::{
+        OpenThread, ResumeThread, SuspendThread, THREAD_SUSPEND_RESUME,
+    };
+
+    let pid = process.pid().as_u32();
+    unsafe {
+        let Ok(snapshot) = CreateToolhelp32Snapshot(TH32CS_SNAPTHREAD, 0) else {
+            return false;
+        };
+        let mut entry = THREADENTRY32 {
+            dwSize: std::mem::size_of::<THREADENTRY32>() as u32,
+            ..Default::default()
+        };
+        let mut changed = false;
+        let mut more = Thread32First(snapshot, &mut entry).is_ok();
+        while more {
+            if entry.th32OwnerProcessID == pid {
+                if let Ok(thread) = OpenThread(THREAD_SUSPEND_RESUME, false, entry.th32ThreadID) {
+                    let previous = if suspend {
+                        SuspendThread(thread)
+                    } else {
+                        ResumeThread(thread)
+                    };
+                    changed |= previous != u32::MAX;
+                    let _ = CloseHandle(thread);
+                }
+            }
+            more = Thread32Next(snapshot, &mut entry).is_ok();
+        }
+        let _ = CloseHandle(snapshot);
+        changed
+    }
+}
+
+#[cfg(not(any(unix, windows)))]
+fn set_suspended(_process: &Process, _suspend: bool) -> bool {
+    false
+}
+
+fn is_same(sys: &System, s: &SuspendedProcess) -> bool {
+    sys.process(Pid::from_u32(s.pid))
+        .is_some_and(|p| p.start_time() == s.start_time)
+}
+
+impl Suspended {
+    fn save(&self, processes: &[SuspendedProcess]) {
+        let Some(path) = &self.path else {
+            return;
+        };
+        if processes.is_empty() {
+            let _ = fs::remove_file(path);
+            return;
+        }
+        if let Some(dir) = path.parent() {
+            let _ = fs::create_dir_all(dir);
+        }
+        if let Ok(json) = serde_json::to_string(processes) {
+            let _ = fs::write(path, json);
+        }
+    }
+
+    // Windows reports no stopped state, so the list is the only record there.
+    pub fn mark(&self, sys: &System, processes: &mut [ProcessInfo]) {
+        let list = self.processes.lock().unwrap();
+        for p in processes.iter_mut() {
+            p.is_suspended |= list.iter().any(|s| s.pid == p.pid && is_same(sys, s));
+        }
+    }
+
+    pub fn resume_all(&self) {
+        let mut list = self.processes.lock().unwrap();
+        if list.is_empty() {
+            return;
+        }
+        let mut sys = System::new();
+        sys.refresh_processes(ProcessesToUpdate::All, true);
+        for s in list.iter().filter(|s| is_same(&sys, s)) {
+            if let Some(process) = sys.process(Pid::from_u32(s.pid)) {
+                set_suspended(process, false);
+            }
+        }
+        list.clear();
+        self.save(&list);
+    }
+}
+
+pub fn load_suspended(app: &AppHandle) -> Suspended {
+    let path = app
+        .path()
+        .app_data_dir()
+        .ok()
+        .map(|dir| dir.join(SUSPENDED_FILE));
+    let processes = path
+        .as_ref()
+        .and_then(|path| fs::read_to_string(path).ok())
+        .and_then(|text| serde_json::from_str(&text).ok())
+        .unwrap_or_default();
+
+    let suspended = Suspended {
+        path,
+        processes: Mutex::new(processes),
+    };
+    // Anything still listed was left behind by a run that didn't exit cleanly.
+    suspended.resume_all();
+    suspended
+}
+
+fn suspend(
+    pid: u32,
+    policy: &ProtectionPolicy,
+    suspended: &Suspended,
+    system: &SharedSystem,
+) -> Result<(), KillError> {
+    let sys = system.refreshed();
+    let process = sys
+        .process(Pid::from_u32(pid))
+        .ok_or_else(KillError::not_found)?;
+    policy.check(&sys, process)?;
+
+    let mut list = suspended.processes.lock().unwrap();
+    list.retain(|s| is_same(&sys, s));
+    // Suspending twice would need two resumes on Windows.
+    if list.iter().any(|s| s.pid == pid) {
+        return Ok(());
+    }
+    if !set_suspended(process, true) {
+        return Err(KillError::Failed {
+            message: "Failed to suspend process — it may require admin rights".into(),
+        });
+    }
+    list.push(SuspendedProcess {
+        pid,
+        name: process.name().to_string_lossy().to_string(),
+        start_time: process.start_time(),
+        suspended_at: now_secs(),
+    });
+    suspended.save(&list);
+    Ok(())
+}
+
+fn resume(
+    pid: u32,
+    policy: &ProtectionPolicy,
+    suspended: &Suspended,
+    system: &SharedSystem,
+) -> Result<(), KillError> {
+    let sys = system.refreshed();
+    let process = sys
+        .process(Pid::from_u32(pid))
+        .ok_or_else(KillError::not_found)?;
+    policy.check(&sys, process)?;
+
+    if !set_suspended(process, false) {
+        return Err(KillError::Failed {
+            message: "Failed to resume process — it may require admin rights".into(),
+        });
+    }
+    let mut list = suspended.processes.lock().unwrap();
+    list.retain(|s| s.pid != pid && is_same(&sys, s));
+    suspended.save(&list);
+    Ok(())
+}
+
+#[tauri::command]
+pub fn suspend_process(
+    app: AppHandle,
+    pid: u32,
+    policy: State<'_, PolicyState>,
+    suspended: State<'_, Suspended>,
+    system: State<'_, SharedSystem>,
+) -> Result<(), KillError> {
+    let policy = policy.lock().unwrap().clone();
+    let name = audit::process_name(pid);
+    let result = suspend(pid, &policy, &suspended, &system);
+    audit::record_result(&app, "suspend", pid, &name, &result, "Process suspended");
+    result
+}
+
+#[tauri::command]
+pub fn resume_process(
+    app: AppHandle,
+    pid: u32,
+    policy: State<'_, PolicyState>,
+    suspended: State<'_, Suspended>,
+    system: State<'_, SharedSystem>,
+) -> Result<(), KillError> {
+    let policy = policy.lock().unwrap().clone();
+    let name = audit::process_name(pid);
+    let result = resume(pid, &policy, &suspended, &system);
+    audit::record_result(&app, "resume", pid, &name, &result, "Process resumed");
+    result
+}
+
+#[tauri::command]
+pub fn get_suspended_processes(
+    suspended: State<'_, Suspended>,
+    system: State<'_, SharedSystem>,
+) -> Vec<SuspendedProcess> {
+    let sys = system.refreshed();
+    let mut list = suspended.processes.lock().unwrap();
+    list.retain(|s| is_same(&sys, s));
+    suspended.save(&list);
+    list.clone()
+}
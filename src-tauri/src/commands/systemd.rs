@@ -0,0 +1,406 @@
+use serde::Serialize;
+use std::cmp::Reverse;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use super::cleanup::{delete_dir_contents_privileged, run_privileged, CleanupResult};
+use super::disk::CleanableItem;
+
+const COREDUMP_DIR: &str = "/var/lib/systemd/coredump";
+const JOURNAL_DIRS: [&str; 2] = ["/var/log/journal", "/run/log/journal"];
+const VACUUM_SIZE_BYTES: u64 = 512 * 1024 * 1024;
+const VACUUM_AGE_DAYS: u64 = 28;
+
+#[derive(Debug, Serialize, Clone)]
+pub struct CoreDumpInfo {
+    pub path: String,
+    pub executable: String,
+    pub pid: u32,
+    pub uid: u32,
+    pub timestamp: u64,
+    pub size_bytes: u64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct JournalUsage {
+    pub path: String,
+    pub persistent: bool,
+    pub total_bytes: u64,
+    pub active_bytes: u64,
+    pub archived_bytes: u64,
+    pub file_count: u32,
+    pub oldest_timestamp: u64,
+    pub vacuum_time_savings: u64,
+    pub vacuum_size_savings: u64,
+}
+
+struct JournalFile {
+    size: u64,
+    modified: SystemTime,
+    archived: bool,
+}
+
+fn unescape_comm(comm: &str) -> String {
+    // systemd-coredump escapes unsafe bytes in the comm as \xNN
+    let bytes = comm.as_bytes();
+    let mut out = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] == b'\\' && i + 3 < bytes.len() && bytes[i + 1] == b'x' {
+            let hex = std::str::from_utf8(&bytes[i + 2..i + 4]).unwrap_or("");
+            if let Ok(b) = u8::from_str_radix(hex, 16) {
+                out.push(b);
+                i += 4;
+                continue;
+            }
+        }
+        out.push(bytes[i]);
+        i += 1;
+    }
+    String::from_utf8_lossy(&out).into()
+}
+
+// core.<comm>.<uid>.<boot id>.<pid>.<usec>[.zst|.xz|.lz4]
+fn parse_coredump_name(name: &str) -> Option<(String, u32, u32, u64)> {
+    let rest = name.strip_prefix("core.")?;
+    let mut parts: Vec<&str> = rest.split('.').collect();
+    if matches!(parts.last(), Some(&"zst") | Some(&"xz") | Some(&"lz4")) {
+        parts.pop();
+    }
+    let n = parts.len();
+    if n < 5 {
+        return None;
+    }
+
+    let usec: u64 = parts[n - 1].parse().ok()?;
+    let pid: u32 = parts[n - 2].parse().ok()?;
+    let boot_id = parts[n - 3];
+    if boot_id.len() != 32 || !boot_id.bytes().all(|b| b.is_ascii_hexdigit()) {
+        return None;
+    }
+    let uid: u32 = parts[n - 4].parse().ok()?;
+    let comm = unescape_comm(&parts[..n - 4].join("."));
+
+    Some((comm, uid, pid, usec / 1_000_000))
+}
+
+fn list_coredumps() -> Vec<CoreDumpInfo> {
+    let mut dumps = Vec::new();
+    if let Ok(entries) = fs::read_dir(COREDUMP_DIR) {
+        for entry in entries.flatten() {
+            let name = entry.file_name().to_string_lossy().to_string();
+            let size = entry.metadata().map(|m| m.len()).unwrap_or(0);
+            if let Some((executable, uid, pid, timestamp)) = parse_coredump_name(&name) {
+                dumps.push(CoreDumpInfo {
+                    path: entry.path().to_string_lossy().into(),
+                    executable,
+                    pid,
+                    uid,
+                    timestamp,
+                    size_bytes: size,
+                });
+            }
+        }
+    }
+    dumps.sort_by_key(|d| Reverse(d.timestamp));
+    dumps
+}
+
+fn journal_root() -> Option<(PathBuf, bool)> {
+    JOURNAL_DIRS
+        .iter()
+        .map(Path::new)
+        .find(|p| p.is_dir())
+        .map(|p| (p.to_path_buf(), p.starts_with("/var")))
+}
+
+fn journal_files(root: &Path) -> Vec<JournalFile> {
+    let mut files = Vec::new();
+    // <root>/<machine-id>/{system,user-1000}[@...].journal[~]
+    let machines = match fs::read_dir(root) {
+        Ok(m) => m,
+        Err(_) => return files,
+    };
+    for machine in machines.flatten() {
+        if let Ok(entries) = fs::read_dir(machine.path()) {
+            for entry in entries.flatten() {
+                let name = entry.file_name().to_string_lossy().to_string();
+                if !name.ends_with(".journal") && !name.ends_with(".journal~") {
+                    continue;
+                }
+                if let Ok(meta) = entry.metadata() {
+                    files.push(JournalFile {
+                        size: meta.len(),
+                        modified: meta.modified().unwrap_or(UNIX_EPOCH),
+                        archived: name.contains('@') || name.ends_with('~'),
+                    });
+                }
+            }
+        }
+    }
+    files.sort_by_key(|f| f.modified);
+    files
+}
+
+// Vacuuming only ever removes archived files, oldest first. Each figure is
+// what that vacuum would remove run on its own, so a size vacuum counts the
+// old files the time vacuum would also take.
+fn vacuum_savings(files: &[JournalFile], cutoff: SystemTime) -> (u64, u64) {
+    let mut by_time = 0u64;
+    let mut by_size = 0u64;
+    let mut remaining: u64 = files.iter().map(|f| f.size).sum();
+    for f in files.iter().filter(|f| f.archived) {
+        if f.modified < cutoff {
+            by_time += f.size;
+        }
+        if remaining > VACUUM_SIZE_BYTES {
+            by_size += f.size;
+            remaining -= f.size;
+        }
+    }
+    (by_time, by_size)
+}
+
+fn journal_usage() -> Option<JournalUsage> {
+    let (root, persistent) = journal_root()?;
+    let files = journal_files(&root);
+    if files.is_empty() {
+        return None;
+    }
+
+    let total: u64 = files.iter().map(|f| f.size).sum();
+    let archived: u64 = files.iter().filter(|f| f.archived).map(|f| f.size).sum();
+    let oldest = files
+        .first()
+        .and_then(|f| f.modified.duration_since(UNIX_EPOCH).ok())
+        .map(|d| d.as_secs())
+        .unwrap_or(0);
+
+    let cutoff = SystemTime::now() - Duration::from_secs(VACUUM_AGE_DAYS * 86_400);
+    let (vacuum_time_savings, vacuum_size_savings) = vacuum_savings(&files, cutoff);
+
+    Some(JournalUsage {
+        path: root.to_string_lossy().into(),
+        persistent,
+        total_bytes: total,
+        active_bytes: total - archived,
+        archived_bytes: archived,
+        file_count: files.len() as u32,
+        oldest_timestamp: oldest,
+        vacuum_time_savings,
+        vacuum_size_savings,
+    })
+}
+
+pub fn scan_coredumps() -> Vec<CleanableItem> {
+    let mut items = Vec::new();
+    let dumps = list_coredumps();
+    let size: u64 = dumps.iter().map(|d| d.size_bytes).sum();
+    if size > 0 {
+        items.push(CleanableItem {
+            id: "coredumps".into(),
+            name: "Core Dumps".into(),
+            description: format!(
+                "{} crash dumps from systemd-coredump, most recently from {}",
+                dumps.len(),
+                dumps[0].executable,
+            ),
+            size_bytes: size,
+            path: COREDUMP_DIR.into(),
+            risk: "safe".into(),
+            category: "System".into(),
+            requires_elevation: true,
+        });
+    }
+    items
+}
+
+pub fn scan_journal() -> Vec<CleanableItem> {
+    let mut items = Vec::new();
+    let usage = match journal_usage() {
+        Some(u) => u,
+        None => return items,
+    };
+
+    if usage.vacuum_time_savings > 1_000_000 {
+        items.push(CleanableItem {
+            id: "journal_vacuum_time".into(),
+            name: "Old Journal Logs".into(),
+            description: format!(
+                "Archived systemd journal files older than {VACUUM_AGE_DAYS} days"
+            ),
+            size_bytes: usage.vacuum_time_savings,
+            path: usage.path.clone(),
+            risk: "moderate".into(),
+            category: "System".into(),
+            requires_elevation: true,
+        });
+    }
+    if usage.vacuum_size_savings > 1_000_000 {
+        items.push(CleanableItem {
+            id: "journal_vacuum_size".into(),
+            name: "Oversized Journal".into(),
+            description: format!(
+                "Trim the systemd journal to {} MB by removing the oldest archived files, whatever their age",
+                VACUUM_SIZE_BYTES / 1_048_576
+            ),
+            size_bytes: usage.vacuum_size_savings,
+            path: usage.path,
+            risk: "moderate".into(),
+            category: "System".into(),
+            requires_elevation: true,
+        });
+    }
+
+    items
+}
+
+fn run_vacuum(max_size_bytes: Option<u64>, max_age_days: Option<u64>) -> (u64, String) {
+    let before = journal_usage().map(|u| u.total_bytes).unwrap_or(0);
+
+    let mut args = Vec::new();
+    if let Some(days) = max_age_days {
+        args.push(format!("--vacuum-time={days}d"));
+    }
+    if let Some(bytes) = max_size_bytes {
+        args.push(format!("--vacuum-size={}K", bytes / 1024));
+    }
+    if args.is_empty() {
+        return (0, "No vacuum limit given".into());
+    }
+
+    let args: Vec<&str> = args.iter().map(String::as_str).collect();
+    let (success, output) = run_privileged("journalctl", &args);
+    // journalctl reports progress on stderr, ending with "Vacuuming done, ..."
+    let last_line = output.lines().last().unwrap_or("").trim().to_string();
+    if !success {
+        return (0, format!("Failed: {last_line}"));
+    }
+
+    let after = journal_usage().map(|u| u.total_bytes).unwrap_or(0);
+    let freed = before.saturating_sub(after);
+    if last_line.is_empty() {
+        (freed, "Journal vacuumed successfully".into())
+    } else {
+        (freed, last_line)
+    }
+}
+
+pub fn vacuum_journal_by_time() -> (u64, String) {
+    run_vacuum(None, Some(VACUUM_AGE_DAYS))
+}
+
+pub fn vacuum_journal_by_size() -> (u64, String) {
+    run_vacuum(Some(VACUUM_SIZE_BYTES), None)
+}
+
+// Always the fixed directory: the deletion runs as root, so it must never
+// follow a path handed in over IPC.
+pub fn clean_coredumps() -> (u64, String) {
+    delete_dir_contents_privileged(Path::new(COREDUMP_DIR))
+}
+
+#[tauri::command]
+pub fn get_coredumps() -> Vec<CoreDumpInfo> {
+    list_coredumps()
+}
+
+#[tauri::command]
+pub fn get_journal_usage() -> Option<JournalUsage> {
+    journal_usage()
+}
+
+#[tauri::command(async)]
+pub fn vacuum_journal(max_size_bytes: Option<u64>, max_age_days: Option<u64>) -> CleanupResult {
+    let (freed, message) = run_vacuum(max_size_bytes, max_age_days);
+    CleanupResult {
+        id: "journal_vacuum".into(),
+        name: "journal vacuum".into(),
+        success: !message.starts_with("Failed") && !message.starts_with("No vacuum"),
+        bytes_freed: freed,
+        message,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BOOT: &str = "6f2e3b5a9c1d4e7f8a0b1c2d3e4f5a6b";
+
+    #[test]
+    fn parses_coredump_names() {
+        let cases = [
+            (
+                format!("core.bash.1000.{BOOT}.4242.1700000000123456.zst"),
+                Some(("bash", 1000, 4242, 1_700_000_000)),
+            ),
+            (
+                format!("core.bash.0.{BOOT}.1.1700000000999999"),
+                Some(("bash", 0, 1, 1_700_000_000)),
+            ),
+            (
+                format!("core.python3.12.1000.{BOOT}.99.1700000000000000.xz"),
+                Some(("python3.12", 1000, 99, 1_700_000_000)),
+            ),
+            (
+                format!("core.Web\\x20Content.1000.{BOOT}.7.1700000000000000.lz4"),
+                Some(("Web Content", 1000, 7, 1_700_000_000)),
+            ),
+            (
+                format!("core.bad\\xzz.1000.{BOOT}.7.1700000000000000"),
+                Some(("bad\\xzz", 1000, 7, 1_700_000_000)),
+            ),
+            // boot id one digit short
+            (
+                format!("core.bash.1000.{}.4242.1700000000123456", &BOOT[1..]),
+                None,
+            ),
+            (format!("core.bash.1000.{BOOT}.pid.1700000000123456"), None),
+            (format!("core.bash.{BOOT}.4242.1700000000123456"), None),
+            ("core.bash.1000".to_string(), None),
+            (
+                format!("vmcore.bash.1000.{BOOT}.4242.1700000000123456"),
+                None,
+            ),
+        ];
+        for (name, expected) in cases {
+            let parsed = parse_coredump_name(&name);
+            assert_eq!(
+                parsed
+                    .as_ref()
+                    .map(|(comm, uid, pid, ts)| (comm.as_str(), *uid, *pid, *ts)),
+                expected,
+                "{name:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn size_vacuum_counts_old_archives_too() {
+        const MB: u64 = 1024 * 1024;
+        let now = SystemTime::now();
+        let cutoff = now - Duration::from_secs(VACUUM_AGE_DAYS * 86_400);
+        let file = |size_mb: u64, age_days: u64, archived: bool| JournalFile {
+            size: size_mb * MB,
+            modified: now - Duration::from_secs(age_days * 86_400),
+            archived,
+        };
+        // Oldest first, as journal_files() sorts them
+        let files = [
+            file(200, 60, true),
+            file(200, 40, true),
+            file(200, 10, true),
+            file(100, 5, true),
+            file(300, 0, false),
+        ];
+        // 1000 MB total: the two old archives go by age, and a 512 MB limit
+        // takes them plus the next oldest
+        assert_eq!(vacuum_savings(&files, cutoff), (400 * MB, 600 * MB));
+
+        // Active files are never removed, however far over the limit
+        let files = [file(100, 60, true), file(900, 0, false)];
+        assert_eq!(vacuum_savings(&files, cutoff), (100 * MB, 100 * MB));
+    }
+}
@@ -0,0 +1,457 @@
+use serde::Serialize;
+use std::env;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::UNIX_EPOCH;
+
+use super::disk::CleanableItem;
+
+const PNG_SIGNATURE: [u8; 8] = [0x89, b'P', b'N', b'G', b'\r', b'\n', 0x1a, b'\n'];
+
+#[derive(Debug, Serialize, Clone)]
+pub struct ThumbnailInfo {
+    pub path: String,
+    pub source_uri: String,
+    pub reason: String, // "missing", "stale"
+    pub size_bytes: u64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ThumbnailScan {
+    pub cache_path: String,
+    pub thumbnail_count: u32,
+    pub total_bytes: u64,
+    pub orphan_bytes: u64,
+    pub orphans: Vec<ThumbnailInfo>,
+    #[serde(skip)]
+    current: Vec<(PathBuf, u64)>, // every other thumbnail, for the cache item
+}
+
+#[derive(Default)]
+struct ThumbMeta {
+    uri: Option<String>,
+    mtime: Option<u64>,
+}
+
+fn thumbnail_dir() -> Option<PathBuf> {
+    let base = env::var("XDG_CACHE_HOME")
+        .ok()
+        .filter(|v| !v.is_empty())
+        .map(PathBuf::from)
+        .or_else(|| {
+            env::var("HOME")
+                .ok()
+                .map(|h| PathBuf::from(h).join(".cache"))
+        })?;
+    let dir = base.join("thumbnails");
+    dir.is_dir().then_some(dir)
+}
+
+fn read_text_chunks(data: &[u8]) -> ThumbMeta {
+    let mut meta = ThumbMeta::default();
+    if data.len() < 8 || data[..8] != PNG_SIGNATURE {
+        return meta;
+    }
+
+    let mut pos = 8;
+    while pos + 8 <= data.len() {
+        let len =
+            u32::from_be_bytes([data[pos], data[pos + 1], data[pos + 2], data[pos + 3]]) as usize;
+        let kind = &data[pos + 4..pos + 8];
+        let start = pos + 8;
+        let end = match start.checked_add(len) {
+            Some(e) if e <= data.len() => e,
+            _ => break,
+        };
+        let chunk = &data[start..end];
+
+        let text = match kind {
+            b"tEXt" => chunk
+                .iter()
+                .position(|&b| b == 0)
+                .map(|nul| (&chunk[..nul], &chunk[nul + 1..])),
+            // iTXt: keyword\0 flag method lang\0 translated\0 text (uncompressed only)
+            b"iTXt" => chunk.iter().position(|&b| b == 0).and_then(|nul| {
+                let rest = chunk.get(nul + 1..)?;
+                if rest.first() != Some(&0) {
+                    return None;
+                }
+                let rest = rest.get(2..)?;
+                let lang_end = rest.iter().position(|&b| b == 0)?;
+                let rest = &rest[lang_end + 1..];
+                let translated_end = rest.iter().position(|&b| b == 0)?;
+                Some((&chunk[..nul], &rest[translated_end + 1..]))
+            }),
+            b"IEND" => break,
+            _ => None,
+        };
+
+        if let Some((key, value)) = text {
+            let value = String::from_utf8_lossy(value).to_string();
+            match key {
+                b"Thumb::URI" => meta.uri = Some(value),
+                b"Thumb::MTime" => meta.mtime = value.trim().parse().ok(),
+                _ => {}
+            }
+        }
+
+        // skip CRC
+        pos = end + 4;
+    }
+
+    meta
+}
+
+fn percent_decode(s: &str) -> Vec<u8> {
+    let bytes = s.as_bytes();
+    let mut out = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] == b'%' && i + 2 < bytes.len() {
+            let hex = std::str::from_utf8(&bytes[i + 1..i + 3]).unwrap_or("");
+            if let Ok(b) = u8::from_str_radix(hex, 16) {
+                out.push(b);
+                i += 3;
+                continue;
+            }
+        }
+        out.push(bytes[i]);
+        i += 1;
+    }
+    out
+}
+
+fn uri_to_path(uri: &str) -> Option<PathBuf> {
+    let rest = uri.strip_prefix("file://")?;
+    // file://host/path — only local files can be checked
+    let path = if rest.starts_with('/') {
+        rest
+    } else {
+        let (host, path) = rest.split_once('/')?;
+        if host != "localhost" {
+            return None;
+        }
+        path
+    };
+    let decoded = percent_decode(path);
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::ffi::OsStrExt;
+        let mut p = PathBuf::from(std::ffi::OsStr::from_bytes(&decoded));
+        if !p.is_absolute() {
+            p = Path::new("/").join(p);
+        }
+        Some(p)
+    }
+
+    #[cfg(not(unix))]
+    {
+        Some(PathBuf::from(String::from_utf8_lossy(&decoded).to_string()))
+    }
+}
+
+fn orphan_reason(meta: &ThumbMeta) -> Option<&'static str> {
+    let source = uri_to_path(meta.uri.as_deref()?)?;
+    // Unreadable sources may still exist, so only NotFound counts as gone,
+    // and only while the folder is there: on unplugged or unmounted media the
+    // whole path disappears with the mount.
+    let source_meta = match fs::metadata(&source) {
+        Ok(m) => m,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => {
+            return source
+                .parent()
+                .is_some_and(Path::is_dir)
+                .then_some("missing");
+        }
+        Err(_) => return None,
+    };
+
+    let thumb_mtime = meta.mtime?;
+    let source_mtime = source_meta
+        .modified()
+        .ok()?
+        .duration_since(UNIX_EPOCH)
+        .ok()?
+        .as_secs();
+    (source_mtime > thumb_mtime).then_some("stale")
+}
+
+fn walk_thumbnails(dir: &Path, depth: u32, scan: &mut ThumbnailScan) {
+    let entries = match fs::read_dir(dir) {
+        Ok(e) => e,
+        Err(_) => return,
+    };
+
+    for entry in entries.flatten() {
+        let path = entry.path();
+        let file_type = match entry.file_type() {
+            Ok(t) => t,
+            Err(_) => continue,
+        };
+        // normal/, large/, x-large/, xx-large/, fail/<app>/
+        if file_type.is_dir() {
+            if depth < 2 {
+                walk_thumbnails(&path, depth + 1, scan);
+            }
+            continue;
+        }
+        if !file_type.is_file() || path.extension().is_none_or(|e| e != "png") {
+            continue;
+        }
+
+        let size = entry.metadata().map(|m| m.len()).unwrap_or(0);
+        scan.thumbnail_count += 1;
+        scan.total_bytes += size;
+
+        let data = match fs::read(&path) {
+            Ok(d) => d,
+            Err(_) => continue,
+        };
+        let meta = read_text_chunks(&data);
+        if let Some(reason) = orphan_reason(&meta) {
+            scan.orphan_bytes += size;
+            scan.orphans.push(ThumbnailInfo {
+                path: path.to_string_lossy().into(),
+                source_uri: meta.uri.unwrap_or_default(),
+                reason: reason.into(),
+                size_bytes: size,
+            });
+        } else {
+            scan.current.push((path, size));
+        }
+    }
+}
+
+fn scan_dir(dir: &Path) -> ThumbnailScan {
+    let mut scan = ThumbnailScan {
+        cache_path: dir.to_string_lossy().into(),
+        thumbnail_count: 0,
+        total_bytes: 0,
+        orphan_bytes: 0,
+        orphans: Vec::new(),
+        current: Vec::new(),
+    };
+    walk_thumbnails(dir, 0, &mut scan);
+    scan.orphans.sort_by(|a, b| a.path.cmp(&b.path));
+    scan
+}
+
+fn scan_thumbnails() -> Option<ThumbnailScan> {
+    Some(scan_dir(&thumbnail_dir()?))
+}
+
+pub fn scan_thumbnail_items() -> Vec<CleanableItem> {
+    let mut items = Vec::new();
+    let scan = match scan_thumbnails() {
+        Some(s) => s,
+        None => return items,
+    };
+
+    if scan.orphan_bytes > 0 {
+        items.push(CleanableItem {
+            id: "thumbnail_orphans".into(),
+            name: "Orphaned Thumbnails".into(),
+            description: format!(
+                "{} previews of files that were deleted or have changed since",
+                scan.orphans.len()
+            ),
+            size_bytes: scan.orphan_bytes,
+            path: scan.cache_path.clone(),
+            risk: "safe".into(),
+            category: "System".into(),
+            requires_elevation: false,
+        });
+    }
+
+    // The two items partition the thumbnails, so selecting both adds up.
+    let remaining = scan.total_bytes - scan.orphan_bytes;
+    if remaining > 0 {
+        items.push(CleanableItem {
+            id: "thumbnail_current".into(),
+            name: "Thumbnail Cache".into(),
+            description: "Previews of your current files — will be recreated as needed".into(),
+            size_bytes: remaining,
+            path: scan.cache_path,
+            risk: "moderate".into(),
+            category: "System".into(),
+            requires_elevation: false,
+        });
+    }
+
+    items
+}
+
+fn remove_files<'a>(files: impl Iterator<Item = (&'a Path, u64)>) -> (u64, String) {
+    let mut freed = 0u64;
+    let mut errors = 0u32;
+    for (path, size) in files {
+        match fs::remove_file(path) {
+            Ok(_) => freed += size,
+            Err(_) => errors += 1,
+        }
+    }
+
+    let msg = if errors > 0 {
+        format!("Cleaned with {errors} files skipped (in use)")
+    } else {
+        "Cleaned successfully".into()
+    };
+    (freed, msg)
+}
+
+pub fn clean_orphaned_thumbnails() -> (u64, String) {
+    match scan_thumbnails() {
+        Some(scan) => remove_files(
+            scan.orphans
+                .iter()
+                .map(|o| (Path::new(&o.path), o.size_bytes)),
+        ),
+        None => (0, "Path not found".into()),
+    }
+}
+
+// Leaves orphans to their own item, so each item frees what it reports.
+pub fn clean_current_thumbnails() -> (u64, String) {
+    match scan_thumbnails() {
+        Some(scan) => remove_files(scan.current.iter().map(|(p, size)| (p.as_path(), *size))),
+        None => (0, "Path not found".into()),
+    }
+}
+
+#[tauri::command]
+pub fn get_orphaned_thumbnails() -> Option<ThumbnailScan> {
+    scan_thumbnails()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // CRCs are left zero; the reader skips them.
+    fn png(chunks: &[(&[u8; 4], &[u8])]) -> Vec<u8> {
+        let mut data = PNG_SIGNATURE.to_vec();
+        for (kind, body) in chunks {
+            data.extend((body.len() as u32).to_be_bytes());
+            data.extend(*kind);
+            data.extend(*body);
+            data.extend([0; 4]);
+        }
+        data
+    }
+
+    fn thumbnail(uri: &str, mtime: u64) -> Vec<u8> {
+        png(&[
+            (b"IHDR", &[0; 13]),
+            (b"tEXt", format!("Thumb::URI\0{uri}").as_bytes()),
+            (b"tEXt", format!("Thumb::MTime\0{mtime}").as_bytes()),
+            (b"IEND", &[]),
+        ])
+    }
+
+    #[test]
+    fn reads_text_chunks() {
+        let meta = read_text_chunks(&thumbnail("file:///home/u/a.jpg", 1700000000));
+        assert_eq!(meta.uri.as_deref(), Some("file:///home/u/a.jpg"));
+        assert_eq!(meta.mtime, Some(1700000000));
+    }
+
+    #[test]
+    fn reads_uncompressed_itxt_only() {
+        let itxt = png(&[(b"iTXt", b"Thumb::URI\0\0\0en\0\0file:///tmp/b.png")]);
+        assert_eq!(
+            read_text_chunks(&itxt).uri.as_deref(),
+            Some("file:///tmp/b.png")
+        );
+
+        let compressed = png(&[(b"iTXt", b"Thumb::URI\0\x01\0\0\0x\x9c")]);
+        assert_eq!(read_text_chunks(&compressed).uri, None);
+    }
+
+    #[test]
+    fn stops_at_truncation_and_iend() {
+        let mut data = png(&[(b"tEXt", b"Thumb::URI\0file:///a")]);
+        let whole = png(&[(b"tEXt", b"Thumb::MTime\x0012")]);
+        data.extend(&whole[8..whole.len() - 6]);
+        let meta = read_text_chunks(&data);
+        assert_eq!(meta.uri.as_deref(), Some("file:///a"));
+        assert_eq!(meta.mtime, None);
+
+        let after_end = png(&[(b"IEND", &[]), (b"tEXt", b"Thumb::URI\0file:///a")]);
+        assert_eq!(read_text_chunks(&after_end).uri, None);
+
+        assert_eq!(read_text_chunks(b"\x89PNG").uri, None);
+        assert_eq!(read_text_chunks(b"GIF89a\0\0\0\0\0\0").uri, None);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn converts_local_file_uris() {
+        let cases = [
+            ("file:///home/u/My%20File.txt", Some("/home/u/My File.txt")),
+            ("file:///home/u/caf%C3%A9.png", Some("/home/u/café.png")),
+            ("file://localhost/tmp/x.png", Some("/tmp/x.png")),
+            ("file:///a%zzb%2", Some("/a%zzb%2")),
+            ("file://server/share/x.png", None),
+            ("smb://server/share/x.png", None),
+            ("trash:///x.png", None),
+            ("/home/u/x.png", None),
+        ];
+        for (uri, expected) in cases {
+            assert_eq!(uri_to_path(uri), expected.map(PathBuf::from), "{uri:?}");
+        }
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn cache_item_leaves_orphans_to_their_own_item() {
+        let root = env::temp_dir().join(format!("thumbnail-test-{}", std::process::id()));
+        let files = root.join("files");
+        let cache = root.join("cache/normal");
+        fs::create_dir_all(&files).unwrap();
+        fs::create_dir_all(&cache).unwrap();
+
+        let kept = files.join("kept.jpg");
+        let changed = files.join("changed.jpg");
+        fs::write(&kept, b"x").unwrap();
+        fs::write(&changed, b"x").unwrap();
+        let uri = |p: &Path| format!("file://{}", p.display());
+        let thumbs = [
+            ("current.png", thumbnail(&uri(&kept), u64::MAX / 2)),
+            ("stale.png", thumbnail(&uri(&changed), 0)),
+            ("missing.png", thumbnail(&uri(&files.join("gone.jpg")), 0)),
+            // its folder is gone too, as on unplugged media
+            (
+                "unmounted.png",
+                thumbnail(&uri(&root.join("media/x.jpg")), 0),
+            ),
+        ];
+        for (name, data) in &thumbs {
+            fs::write(cache.join(name), data).unwrap();
+        }
+
+        let scan = scan_dir(&root.join("cache"));
+        let mut reasons: Vec<(&str, &str)> = scan
+            .orphans
+            .iter()
+            .map(|o| (o.path.rsplit('/').next().unwrap(), o.reason.as_str()))
+            .collect();
+        reasons.sort();
+        assert_eq!(
+            reasons,
+            [("missing.png", "missing"), ("stale.png", "stale")]
+        );
+        let current_bytes: u64 = scan.current.iter().map(|(_, size)| size).sum();
+        assert_eq!(scan.thumbnail_count, 4);
+        assert_eq!(scan.orphan_bytes + current_bytes, scan.total_bytes);
+
+        let (freed, _) = remove_files(scan.current.iter().map(|(p, s)| (p.as_path(), *s)));
+        assert_eq!(freed, current_bytes);
+        let left = scan_dir(&root.join("cache"));
+        assert_eq!(left.thumbnail_count, 2);
+        assert_eq!(left.orphan_bytes, scan.orphan_bytes);
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+}
@@ -0,0 +1,429 @@
+use serde::Serialize;
+use std::cmp::Reverse;
+use tauri::State;
+
+use super::disk::CleanableItem;
+use super::memory::SharedSystem;
+
+#[cfg(target_os = "linux")]
+use std::collections::{HashMap, HashSet};
+#[cfg(target_os = "linux")]
+use std::fs;
+#[cfg(target_os = "linux")]
+use std::os::unix::fs::MetadataExt;
+#[cfg(target_os = "linux")]
+use std::path::{Path, PathBuf};
+#[cfg(target_os = "linux")]
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+#[cfg(target_os = "linux")]
+use sysinfo::{Pid, Users};
+
+#[cfg(target_os = "linux")]
+const LARGEST_FILES: usize = 20;
+#[cfg(target_os = "linux")]
+const STALE_DAYS: u64 = 7;
+
+// Only these are scratch space. /run and /run/user hold the live state of
+// services and sessions, so files there are listed but never offered.
+#[cfg(target_os = "linux")]
+const CLEANABLE_MOUNTS: [&str; 2] = ["/dev/shm", "/tmp"];
+
+#[derive(Debug, Serialize, Clone)]
+pub struct FileHolder {
+    pub pid: u32,
+    pub name: String,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct TmpfsFile {
+    pub path: String,
+    pub size_bytes: u64, // pages actually allocated, so sparse files count what they use
+    pub owner: Option<String>,
+    pub modified: u64,
+    pub holders: Vec<FileHolder>, // open or mapped by these processes
+    pub stale: bool,
+}
+
+#[derive(Debug, Serialize)]
+pub struct TmpfsMount {
+    pub mount_point: String,
+    pub size_bytes: u64,
+    pub used_bytes: u64,
+    pub file_count: u32,
+    pub stale_bytes: u64,
+    pub largest_files: Vec<TmpfsFile>,
+}
+
+#[cfg(target_os = "linux")]
+struct ScannedFile {
+    path: PathBuf,
+    size_bytes: u64,
+    uid: u32,
+    modified: u64,
+    stale: bool,
+}
+
+#[cfg(target_os = "linux")]
+struct ScannedMount {
+    mount_point: PathBuf,
+    size_bytes: u64,
+    used_bytes: u64,
+    files: Vec<ScannedFile>,
+}
+
+// /proc/mounts escapes spaces, tabs, newlines and backslashes as \ooo.
+#[cfg(target_os = "linux")]
+fn unescape_mount(field: &str) -> String {
+    let bytes = field.as_bytes();
+    let mut out = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] == b'\\' && i + 3 < bytes.len() {
+            let octal = std::str::from_utf8(&bytes[i + 1..i + 4]).unwrap_or("");
+            if let Ok(b) = u8::from_str_radix(octal, 8) {
+                out.push(b);
+                i += 4;
+                continue;
+            }
+        }
+        out.push(bytes[i]);
+        i += 1;
+    }
+    String::from_utf8_lossy(&out).to_string()
+}
+
+#[cfg(target_os = "linux")]
+fn tmpfs_mounts() -> Vec<PathBuf> {
+    let text = fs::read_to_string("/proc/mounts").unwrap_or_default();
+    let mut seen = HashSet::new();
+    text.lines()
+        .filter_map(|line| {
+            let mut fields = line.split_whitespace();
+            let (_, mount_point, fstype) = (fields.next()?, fields.next()?, fields.next()?);
+            (fstype == "tmpfs").then(|| PathBuf::from(unescape_mount(mount_point)))
+        })
+        // A mount point stacked twice shows up twice; only the top one is visible
+        .filter(|path| seen.insert(path.clone()))
+        .collect()
+}
+
+#[cfg(target_os = "linux")]
+fn mount_usage(path: &Path) -> Option<(u64, u64)> {
+    use std::os::unix::ffi::OsStrExt;
+    let c_path = std::ffi::CString::new(path.as_os_str().as_bytes()).ok()?;
+    let mut stat: libc::statvfs = unsafe { std::mem::zeroed() };
+    if unsafe { libc::statvfs(c_path.as_ptr(), &mut stat) } != 0 {
+        return None;
+    }
+    let block = stat.f_frsize as u64;
+    let size = stat.f_blocks as u64 * block;
+    let used = (stat.f_blocks as u64).saturating_sub(stat.f_bfree as u64) * block;
+    Some((size, used))
+}
+
+// "7f..-7f.. rw-s 00000000 00:1a 1234   /dev/shm/name"; anonymous and
+// [heap]-style mappings have no path.
+#[cfg(target_os = "linux")]
+fn maps_path(line: &str) -> Option<&str> {
+    line.find(" /").map(|at| line[at + 1..].trim())
+}
+
+// Path -> PIDs with the file open or mapped. Shared memory is usually
+// mmap()ed and the descriptor closed right after, so fds alone miss it.
+#[cfg(target_os = "linux")]
+fn file_holders(mounts: &[PathBuf]) -> HashMap<PathBuf, Vec<u32>> {
+    let mut holders: HashMap<PathBuf, Vec<u32>> = HashMap::new();
+    let Ok(entries) = fs::read_dir("/proc") else {
+        return holders;
+    };
+    let pids = entries
+        .flatten()
+        .filter_map(|e| e.file_name().to_str()?.parse::<u32>().ok());
+
+    for pid in pids {
+        let mut paths = HashSet::new();
+        if let Ok(fds) = fs::read_dir(format!("/proc/{pid}/fd")) {
+            paths.extend(fds.flatten().filter_map(|fd| fs::read_link(fd.path()).ok()));
+        }
+        if let Ok(maps) = fs::read_to_string(format!("/proc/{pid}/maps")) {
+            paths.extend(maps.lines().filter_map(maps_path).map(PathBuf::from));
+        }
+        for path in paths {
+            // Deleted files read "/dev/shm/x (deleted)" and match nothing we walk
+            if mounts.iter().any(|m| path.starts_with(m)) {
+                holders.entry(path).or_default().push(pid);
+            }
+        }
+    }
+    holders
+}
+
+#[cfg(target_os = "linux")]
+fn walk_mount(root: &Path, holders: &HashMap<PathBuf, Vec<u32>>) -> Vec<ScannedFile> {
+    let mut files = Vec::new();
+    let Ok(root_meta) = fs::symlink_metadata(root) else {
+        return files;
+    };
+    let cleanable = CLEANABLE_MOUNTS.iter().any(|m| root == Path::new(m));
+    let euid = unsafe { libc::geteuid() };
+    let cutoff = SystemTime::now()
+        .checked_sub(Duration::from_secs(STALE_DAYS * 86400))
+        .and_then(|t| t.duration_since(UNIX_EPOCH).ok())
+        .map_or(0, |d| d.as_secs() as i64);
+
+    let mut dirs = vec![root.to_path_buf()];
+    while let Some(dir) = dirs.pop() {
+        let Ok(entries) = fs::read_dir(&dir) else {
+            continue;
+        };
+        for entry in entries.flatten() {
+            let Ok(meta) = entry.metadata() else {
+                continue;
+            };
+            // Stay on this mount; /run/user/<uid> is its own tmpfs below /run
+            if meta.dev() != root_meta.dev() {
+                continue;
+            }
+            if meta.is_dir() {
+                dirs.push(entry.path());
+                continue;
+            }
+            if !meta.is_file() {
+                continue;
+            }
+
+            let path = entry.path();
+            let stale = cleanable
+                && meta.uid() == euid
+                && meta.mtime().max(meta.atime()) < cutoff
+                && !holders.contains_key(&path);
+            files.push(ScannedFile {
+                path,
+                size_bytes: meta.blocks() * 512,
+                uid: meta.uid(),
+                modified: meta.mtime().max(0) as u64,
+                stale,
+            });
+        }
+    }
+    files
+}
+
+#[cfg(target_os = "linux")]
+fn scan_mounts(holders: &HashMap<PathBuf, Vec<u32>>, mounts: &[PathBuf]) -> Vec<ScannedMount> {
+    mounts
+        .iter()
+        .filter_map(|mount_point| {
+            let (size_bytes, used_bytes) = mount_usage(mount_point)?;
+            Some(ScannedMount {
+                mount_point: mount_point.clone(),
+                size_bytes,
+                used_bytes,
+                files: walk_mount(mount_point, holders),
+            })
+        })
+        .collect()
+}
+
+// The cleanable mounts that really are tmpfs and could be read, and the stale
+// files on them. /tmp on disk is left to the regular temp file cleanup.
+#[cfg(target_os = "linux")]
+fn stale_files() -> (Vec<PathBuf>, Vec<ScannedFile>) {
+    let mounts: Vec<PathBuf> = tmpfs_mounts()
+        .into_iter()
+        .filter(|m| CLEANABLE_MOUNTS.iter().any(|c| m == Path::new(c)))
+        .collect();
+    let holders = file_holders(&mounts);
+    let scanned = scan_mounts(&holders, &mounts);
+    let mount_points = scanned.iter().map(|m| m.mount_point.clone()).collect();
+    let files = scanned
+        .into_iter()
+        .flat_map(|mount| mount.files)
+        .filter(|file| file.stale)
+        .collect();
+    (mount_points, files)
+}
+
+#[cfg(target_os = "linux")]
+pub fn scan_tmpfs_items() -> Vec<CleanableItem> {
+    let (mounts, stale) = stale_files();
+    if stale.is_empty() {
+        return Vec::new();
+    }
+
+    let mounts: Vec<String> = mounts
+        .iter()
+        .map(|m| m.to_string_lossy().to_string())
+        .collect();
+    let size_bytes = stale.iter().map(|f| f.size_bytes).sum();
+    vec![CleanableItem {
+        id: "tmpfs_stale".into(),
+        name: "Stale Files in RAM".into(),
+        description: format!(
+            "{} files in {} untouched for {STALE_DAYS} days and not in use — frees memory, not disk",
+            stale.len(),
+            mounts.join(" and ")
+        ),
+        size_bytes,
+        path: mounts.join(", "),
+        risk: "moderate".into(),
+        category: "Memory".into(),
+        requires_elevation: false,
+    }]
+}
+
+#[cfg(not(target_os = "linux"))]
+pub fn scan_tmpfs_items() -> Vec<CleanableItem> {
+    Vec::new()
+}
+
+// Rescans instead of trusting the earlier listing, so a file that was opened
+// since is left alone.
+#[cfg(target_os = "linux")]
+pub fn clean_stale_files() -> (u64, String) {
+    let mut freed = 0u64;
+    let mut errors = 0u32;
+    let (_, stale) = stale_files();
+    for file in stale {
+        match fs::remove_file(&file.path) {
+            Ok(_) => freed += file.size_bytes,
+            Err(_) => errors += 1,
+        }
+    }
+
+    let msg = if errors > 0 {
+        format!("Cleaned with {errors} files skipped")
+    } else {
+        "Cleaned successfully".into()
+    };
+    (freed, msg)
+}
+
+#[cfg(not(target_os = "linux"))]
+pub fn clean_stale_files() -> (u64, String) {
+    (0, "Not supported on this platform".into())
+}
+
+#[cfg(target_os = "linux")]
+fn read_tmpfs_usage(system: &SharedSystem) -> Vec<TmpfsMount> {
+    let mounts = tmpfs_mounts();
+    let holders = file_holders(&mounts);
+    let scanned = scan_mounts(&holders, &mounts);
+
+    let users: HashMap<u32, String> = Users::new_with_refreshed_list()
+        .iter()
+        .map(|u| (**u.id(), u.name().to_string()))
+        .collect();
+    let sys = system.refreshed();
+
+    scanned
+        .into_iter()
+        .map(|mount| {
+            let file_count = mount.files.len() as u32;
+            let stale_bytes = mount
+                .files
+                .iter()
+                .filter(|f| f.stale)
+                .map(|f| f.size_bytes)
+                .sum();
+            let mut files = mount.files;
+            files.sort_by_key(|f| Reverse(f.size_bytes));
+            files.truncate(LARGEST_FILES);
+
+            let largest_files = files
+                .into_iter()
+                .map(|file| {
+                    let holders = holders
+                        .get(&file.path)
+                        .into_iter()
+                        .flatten()
+                        .map(|&pid| FileHolder {
+                            pid,
+                            name: sys
+                                .process(Pid::from_u32(pid))
+                                .map(|p| p.name().to_string_lossy().to_string())
+                                .unwrap_or_default(),
+                        })
+                        .collect();
+                    TmpfsFile {
+                        path: file.path.to_string_lossy().into(),
+                        size_bytes: file.size_bytes,
+                        owner: users.get(&file.uid).cloned(),
+                        modified: file.modified,
+                        holders,
+                        stale: file.stale,
+                    }
+                })
+                .collect();
+
+            TmpfsMount {
+                mount_point: mount.mount_point.to_string_lossy().into(),
+                size_bytes: mount.size_bytes,
+                used_bytes: mount.used_bytes,
+                file_count,
+                stale_bytes,
+                largest_files,
+            }
+        })
+        .collect()
+}
+
+#[cfg(not(target_os = "linux"))]
+fn read_tmpfs_usage(_system: &SharedSystem) -> Vec<TmpfsMount> {
+    Vec::new()
+}
+
+#[tauri::command]
+pub fn get_tmpfs_usage(system: State<'_, SharedSystem>) -> Vec<TmpfsMount> {
+    let mut mounts = read_tmpfs_usage(&system);
+    mounts.sort_by_key(|m| Reverse(m.used_bytes));
+    mounts
+}
+
+#[cfg(all(test, target_os = "linux"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unescapes_mount_points() {
+        let cases = [
+            ("/dev/shm", "/dev/shm"),
+            ("/mnt/my\\040disk", "/mnt/my disk"),
+            ("/mnt/tab\\011here", "/mnt/tab\there"),
+            ("/mnt/new\\012line", "/mnt/new\nline"),
+            ("/mnt/back\\134slash", "/mnt/back\\slash"),
+            ("/mnt/\\040\\040", "/mnt/  "),
+            // not a full escape, kept as is
+            ("/mnt/end\\", "/mnt/end\\"),
+            ("/mnt/short\\04", "/mnt/short\\04"),
+            ("/mnt/bad\\9xy", "/mnt/bad\\9xy"),
+            ("/mnt/big\\777x", "/mnt/big\\777x"),
+        ];
+        for (field, expected) in cases {
+            assert_eq!(unescape_mount(field), expected, "{field:?}");
+        }
+    }
+
+    #[test]
+    fn reads_paths_from_maps_lines() {
+        let cases = [
+            (
+                "7f1c2a000000-7f1c2a400000 rw-s 00000000 00:1a 1234                       /dev/shm/pulse-shm-123",
+                Some("/dev/shm/pulse-shm-123"),
+            ),
+            (
+                "7f1c2a000000-7f1c2a400000 rw-s 00000000 00:1a 1234 /dev/shm/name (deleted)",
+                Some("/dev/shm/name (deleted)"),
+            ),
+            (
+                "7f1c2a000000-7f1c2a400000 r--p 00000000 00:1a 99 /tmp/dir with spaces/f",
+                Some("/tmp/dir with spaces/f"),
+            ),
+            ("55d4c8e2a000-55d4c8e4b000 rw-p 00000000 00:00 0                          [heap]", None),
+            ("7f1c2a000000-7f1c2a400000 rw-p 00000000 00:00 0 ", None),
+        ];
+        for (line, expected) in cases {
+            assert_eq!(maps_path(line), expected, "{line:?}");
+        }
+    }
+}
@@ -1,23 +1,80 @@
 mod commands;
 
-use commands::{cleanup, disk, memory, recommendations, startup};
+use std::sync::Mutex;
+use tauri::Manager;
+
+use commands::{
+    audit, categories, cgroups, cleanup, disk, flatpak, logs, memory, network, pressure, priority,
+    protection, recommendations, sampler, snap, startup, suspend, systemd, thumbnails, tmpfs,
+};
 
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
     tauri::Builder::default()
         .plugin(tauri_plugin_shell::init())
+        .setup(|app| {
+            let policy = protection::load_policy(app.handle());
+            app.manage(Mutex::new(policy));
+            let categorizer = categories::load_categorizer(app.handle());
+            app.manage(Mutex::new(categorizer));
+            app.manage(sampler::load_sampler(app.handle()));
+            app.manage(suspend::load_suspended(app.handle()));
+            app.manage(memory::SharedSystem::default());
+            sampler::start_sampler(app.handle().clone());
+            Ok(())
+        })
         .invoke_handler(tauri::generate_handler![
             disk::scan_disk,
             disk::get_drive_info,
             memory::get_memory_info,
             memory::get_processes,
+            memory::get_process_tree,
+            memory::get_process_groups,
+            memory::get_process_details,
             memory::kill_process,
+            memory::kill_process_tree,
+            memory::kill_process_group,
+            suspend::suspend_process,
+            suspend::resume_process,
+            suspend::get_suspended_processes,
+            priority::set_process_priority,
+            priority::set_process_affinity,
+            cgroups::set_memory_cap,
+            cgroups::get_memory_caps,
+            cgroups::remove_memory_cap,
+            audit::get_audit_log,
+            pressure::get_oom_events,
+            network::get_network_connections,
+            tmpfs::get_tmpfs_usage,
             memory::get_vm_info,
+            sampler::get_memory_history,
+            sampler::get_sampler_config,
+            sampler::set_sampler_config,
+            protection::get_protection_policy,
+            protection::set_protection_policy,
+            categories::get_category_rules,
+            categories::set_category_rules,
             startup::get_startup_items,
             startup::toggle_startup_item,
             cleanup::clean_items,
+            logs::scan_log_retention,
+            logs::apply_log_retention,
+            systemd::get_coredumps,
+            systemd::get_journal_usage,
+            systemd::vacuum_journal,
+            thumbnails::get_orphaned_thumbnails,
+            flatpak::get_flatpak_runtimes,
+            flatpak::remove_flatpak_runtime,
+            snap::get_snap_revisions,
+            snap::remove_snap_revision,
             recommendations::get_recommendations,
         ])
-        .run(tauri::generate_context!())
-        .expect("error while running tauri application");
+        .build(tauri::generate_context!())
+        .expect("error while running tauri application")
+        .run(|app, event| {
+            // Nothing the app paused should stay frozen after it closes.
+            if let tauri::RunEvent::Exit = event {
+                app.state::<suspend::Suspended>().resume_all();
+            }
+        });
 }
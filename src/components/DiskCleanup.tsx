@@ -212,6 +212,11 @@ export default function DiskCleanup() {
                       <div className="flex items-center gap-2">
                         <span className="font-medium text-sm">{item.name}</span>
                         <span className={riskBadge[item.risk]}>{riskLabel[item.risk]}</span>
+                        {item.requires_elevation && (
+                          <span className="badge badge-info" title="You will be asked for your password">
+                            Admin
+                          </span>
+                        )}
                       </div>
                       <p className="text-xs truncate mt-0.5" style={{ color: "var(--text-secondary)" }}>
                         {item.description}
@@ -15,10 +15,28 @@ import {
   XCircle,
   Server,
   ChevronDown,
+  Layers,
+  Network,
+  Pause,
+  Play,
 } from "lucide-react";
 import { api } from "../lib/api";
 import { formatBytes } from "../lib/format";
-import type { MemoryInfo, ProcessInfo, VmInfo } from "../lib/types";
+import type {
+  CommandError,
+  MemoryCap,
+  MemoryInfo,
+  MemoryMetric,
+  NetworkConnection,
+  OomReport,
+  ProcessGroup,
+  ProcessInfo,
+  ProcessSortKey,
+  TmpfsMount,
+  VmInfo,
+} from "../lib/types";
+
+const PAGE_SIZE = 50;
 
 const categoryColors: Record<string, string> = {
   Browser: "var(--cat-browser)",
@@ -32,22 +50,56 @@ const categoryColors: Record<string, string> = {
 export default function MemoryPanel() {
   const [memory, setMemory] = useState<MemoryInfo | null>(null);
   const [processes, setProcesses] = useState<ProcessInfo[]>([]);
+  const [flagged, setFlagged] = useState<ProcessInfo[]>([]);
+  const [total, setTotal] = useState(0);
+  const [filter, setFilter] = useState("");
+  const [sortBy, setSortBy] = useState<ProcessSortKey>("memory");
+  const [limit, setLimit] = useState(PAGE_SIZE);
   const [vms, setVms] = useState<VmInfo[]>([]);
+  const [oom, setOom] = useState<OomReport | null>(null);
+  const [connections, setConnections] = useState<NetworkConnection[] | null>(null);
+  const [connectionsError, setConnectionsError] = useState<string | null>(null);
+  const [caps, setCaps] = useState<MemoryCap[]>([]);
+  const [tmpfs, setTmpfs] = useState<TmpfsMount[]>([]);
+  const [portFilter, setPortFilter] = useState("");
   const [loading, setLoading] = useState(true);
   const [killing, setKilling] = useState<number | null>(null);
   const [showAll, setShowAll] = useState(false);
+  const [grouped, setGrouped] = useState(false);
+  const [groups, setGroups] = useState<ProcessGroup[]>([]);
+  const [metric, setMetric] = useState<MemoryMetric>("rss");
+
+  const loadProcesses = async () => {
+    const page = await api.getProcesses({
+      filter,
+      sort_by: sortBy,
+      metric,
+      accurate: metric !== "rss",
+      limit,
+    });
+    setProcesses(page.processes);
+    setFlagged(page.flagged);
+    setTotal(page.total);
+  };
 
   const refresh = async () => {
     setLoading(true);
     try {
-      const [m, p, v] = await Promise.all([
+      const [m, , v, o, c, t, g] = await Promise.all([
         api.getMemoryInfo(),
-        api.getProcesses(),
+        loadProcesses(),
         api.getVmInfo(),
+        api.getOomEvents(5),
+        api.getMemoryCaps(),
+        api.getTmpfsUsage(),
+        grouped ? api.getProcessGroups() : Promise.resolve(null),
       ]);
       setMemory(m);
-      setProcesses(p);
       setVms(v);
+      setOom(o);
+      setCaps(c);
+      setTmpfs(t);
+      if (g) setGroups(g);
     } catch (e) {
       console.error(e);
     }
@@ -58,17 +110,57 @@ export default function MemoryPanel() {
     refresh();
   }, []);
 
+  useEffect(() => {
+    loadProcesses().catch(console.error);
+  }, [filter, sortBy, metric, limit]);
+
+  useEffect(() => {
+    if (grouped) api.getProcessGroups().then(setGroups).catch(console.error);
+  }, [grouped]);
+
   const killProc = async (pid: number) => {
     setKilling(pid);
     try {
       await api.killProcess(pid);
       await refresh();
+      if (connections) setConnections(await api.getNetworkConnections());
+    } catch (e) {
+      console.error(e);
+    }
+    setKilling(null);
+  };
+
+  const toggleSuspend = async (p: ProcessInfo) => {
+    setKilling(p.pid);
+    try {
+      await (p.is_suspended ? api.resumeProcess(p.pid) : api.suspendProcess(p.pid));
+      await refresh();
+    } catch (e) {
+      console.error(e);
+    }
+    setKilling(null);
+  };
+
+  const killGroup = async (group: ProcessGroup) => {
+    setKilling(group.pids[0]);
+    try {
+      await api.killProcessGroup(group.key);
+      await refresh();
     } catch (e) {
       console.error(e);
     }
     setKilling(null);
   };
 
+  const metricBytes = (p: ProcessInfo) =>
+    metric === "pss"
+      ? p.pss_bytes ?? p.memory_bytes
+      : metric === "uss"
+        ? p.uss_bytes ?? p.memory_bytes
+        : metric === "swap"
+          ? p.swap_bytes ?? 0
+          : p.memory_bytes;
+
   const byCategory = processes.reduce(
     (acc, p) => {
       acc[p.category] = (acc[p.category] || 0) + p.memory_bytes;
@@ -81,8 +173,51 @@ export default function MemoryPanel() {
     .map(([name, value]) => ({ name, value }))
     .sort((a, b) => b.value - a.value);
 
-  const zombies = processes.filter((p) => p.is_zombie);
-  const visibleProcesses = showAll ? processes : processes.slice(0, 12);
+  const usedTmpfs = tmpfs.filter((m) => m.used_bytes > 0 && m.size_bytes > 0);
+
+  const liftCap = async (unit: string) => {
+    try {
+      await api.removeMemoryCap(unit);
+      setCaps(await api.getMemoryCaps());
+    } catch (e) {
+      console.error(e);
+    }
+  };
+
+  const toggleConnections = async () => {
+    if (connections || connectionsError) {
+      setConnections(null);
+      setConnectionsError(null);
+      return;
+    }
+    try {
+      setConnections(await api.getNetworkConnections());
+    } catch (e) {
+      const err = e as CommandError;
+      if (err.kind === "unsupported") setConnectionsError(err.message);
+      else console.error(e);
+    }
+  };
+
+  // A number matches the local port exactly; anything else the process name
+  const portQuery = portFilter.trim().toLowerCase();
+  const visibleConnections = (connections ?? []).filter(
+    (c) =>
+      !portQuery ||
+      (/^\d+$/.test(portQuery)
+        ? c.local_port === Number(portQuery)
+        : (c.process_name ?? "").toLowerCase().includes(portQuery))
+  );
+
+  const problems = flagged.filter((p) => p.problem);
+  // Suspended processes stay reachable even when they sort off the page
+  const listed = [
+    ...processes,
+    ...flagged.filter((f) => f.is_suspended && !processes.some((p) => p.pid === f.pid)),
+  ];
+  const visibleProcesses = showAll ? listed : listed.slice(0, 12);
+  const visibleGroups = showAll ? groups : groups.slice(0, 12);
+  const listLength = grouped ? groups.length : listed.length;
 
   return (
     <div className="px-6 pt-5 pb-6 space-y-4 overflow-y-auto h-full">
@@ -115,9 +250,9 @@ export default function MemoryPanel() {
                   </div>
                 </div>
                 <div>
-                  <div className="text-xs font-medium mb-1" style={{ color: "var(--text-muted)" }}>Free</div>
+                  <div className="text-xs font-medium mb-1" style={{ color: "var(--text-muted)" }}>Available</div>
                   <div className="text-2xl font-bold" style={{ color: "var(--success)" }}>
-                    {formatBytes(memory.free_bytes)}
+                    {formatBytes(memory.available_bytes)}
                   </div>
                 </div>
                 <div>
@@ -150,6 +285,55 @@ export default function MemoryPanel() {
                   {memory.usage_percent.toFixed(1)}%
                 </span>
               </div>
+              <div className="flex flex-wrap gap-x-4 gap-y-1 mt-3 text-xs" style={{ color: "var(--text-muted)" }}>
+                <span>Cache {formatBytes(memory.cached_bytes + memory.buffers_bytes)}</span>
+                <span>Shared {formatBytes(memory.shared_bytes)}</span>
+                {memory.swap_total_bytes > 0 && (
+                  <span>
+                    Swap {formatBytes(memory.swap_used_bytes)} / {formatBytes(memory.swap_total_bytes)}
+                  </span>
+                )}
+                {memory.commit_limit_bytes > 0 && (
+                  <span>
+                    Committed {formatBytes(memory.committed_bytes)} / {formatBytes(memory.commit_limit_bytes)}
+                  </span>
+                )}
+                {memory.huge_pages_total > 0 && (
+                  <span>
+                    Huge pages {memory.huge_pages_total - memory.huge_pages_free} / {memory.huge_pages_total}
+                  </span>
+                )}
+                {memory.pressure && (
+                  <span title="Share of the last minute programs spent waiting for memory">
+                    Stalled {memory.pressure.some_avg60.toFixed(1)}%
+                  </span>
+                )}
+              </div>
+            </div>
+          )}
+
+          {oom && (oom.events.length > 0 || oom.source === "unavailable") && (
+            <div className="card p-4">
+              <h3 className="font-semibold text-sm mb-2">Killed for Lack of Memory</h3>
+              {oom.source === "unavailable" && (
+                <p className="text-xs" style={{ color: "var(--text-muted)" }}>
+                  Can't read the kernel log, so out-of-memory kills can't be shown. Run as administrator
+                  or join the systemd-journal group to see them.
+                </p>
+              )}
+              <div className="space-y-1">
+                {oom.events.map((e) => (
+                  <div key={`${e.timestamp}-${e.pid}`} className="flex items-center gap-3 text-xs">
+                    <span className="font-mono w-36" style={{ color: "var(--text-muted)" }}>
+                      {new Date(e.timestamp * 1000).toLocaleString()}
+                    </span>
+                    <span className="flex-1 truncate">
+                      {e.name} <span style={{ color: "var(--text-muted)" }}>({e.pid})</span>
+                    </span>
+                    <span className="font-mono">{formatBytes(e.rss_bytes)}</span>
+                  </div>
+                ))}
+              </div>
             </div>
           )}
 
@@ -219,18 +403,107 @@ export default function MemoryPanel() {
             </div>
           )}
 
-          {/* Zombie processes */}
-          {zombies.length > 0 && (
+          {/* Memory caps */}
+          {caps.length > 0 && (
+            <div className="card p-4">
+              <h3 className="font-semibold text-sm mb-2">Memory Caps</h3>
+              <div className="space-y-2">
+                {caps.map((cap) => {
+                  const limit = cap.max_bytes ?? cap.high_bytes ?? 0;
+                  const pct = limit > 0 ? Math.min(100, (cap.memory_bytes / limit) * 100) : 0;
+                  return (
+                    <div key={cap.unit} className="text-xs">
+                      <div className="flex items-center gap-3 mb-1">
+                        <span className="font-medium flex-1 truncate" title={cap.unit}>
+                          {cap.names.length > 0 ? cap.names.join(", ") : cap.unit}
+                        </span>
+                        <span className="font-mono" style={{ color: "var(--text-secondary)" }}>
+                          {formatBytes(cap.memory_bytes)} / {formatBytes(limit)}
+                        </span>
+                        {cap.oom_kills > 0 && (
+                          <span className="badge badge-high">{cap.oom_kills} killed</span>
+                        )}
+                        <button onClick={() => liftCap(cap.unit)} className="btn btn-ghost btn-pill">
+                          Lift
+                        </button>
+                      </div>
+                      <div className="progress-track">
+                        <div
+                          className="progress-fill"
+                          style={{
+                            width: `${pct}%`,
+                            background: pct > 90 ? "var(--danger)" : "var(--accent)",
+                          }}
+                        />
+                      </div>
+                    </div>
+                  );
+                })}
+              </div>
+            </div>
+          )}
+
+          {/* Files held in RAM by tmpfs mounts */}
+          {usedTmpfs.length > 0 && (
+            <div className="card p-4">
+              <h3 className="font-semibold text-sm mb-2">RAM-backed Folders</h3>
+              <div className="space-y-3">
+                {usedTmpfs.map((m) => (
+                  <div key={m.mount_point} className="text-xs">
+                    <div className="flex items-center gap-3 mb-1">
+                      <span className="font-medium font-mono flex-1 truncate">{m.mount_point}</span>
+                      <span style={{ color: "var(--text-muted)" }}>{m.file_count} files</span>
+                      {m.stale_bytes > 0 && (
+                        <span className="badge badge-info" title="Offered in Disk Cleanup">
+                          {formatBytes(m.stale_bytes)} stale
+                        </span>
+                      )}
+                      <span className="font-mono" style={{ color: "var(--text-secondary)" }}>
+                        {formatBytes(m.used_bytes)} / {formatBytes(m.size_bytes)}
+                      </span>
+                    </div>
+                    <div className="progress-track mb-1.5">
+                      <div
+                        className="progress-fill"
+                        style={{ width: `${Math.min(100, (m.used_bytes / m.size_bytes) * 100)}%` }}
+                      />
+                    </div>
+                    {m.largest_files.slice(0, 5).map((f) => (
+                      <div key={f.path} className="flex items-center gap-3 py-0.5">
+                        <span className="font-mono flex-1 truncate" title={f.path}>
+                          {f.path}
+                        </span>
+                        <span className="truncate" style={{ color: "var(--text-muted)" }}>
+                          {f.holders.length > 0
+                            ? f.holders.map((h) => `${h.name} (${h.pid})`).join(", ")
+                            : f.owner ?? "—"}
+                        </span>
+                        <span className="font-mono" style={{ color: "var(--text-secondary)" }}>
+                          {formatBytes(f.size_bytes)}
+                        </span>
+                      </div>
+                    ))}
+                  </div>
+                ))}
+              </div>
+            </div>
+          )}
+
+          {/* Problem processes */}
+          {problems.length > 0 && (
             <div>
               <h3 className="font-semibold text-sm mb-2 flex items-center gap-2" style={{ color: "var(--warning)" }}>
                 <Skull size={15} /> Potential Problem Processes
               </h3>
               <div className="space-y-1.5">
-                {zombies.map((p) => (
+                {problems.map((p) => (
                   <div key={p.pid} className="row-item accent-left-yellow">
                     <div className="flex-1 min-w-0">
                       <span className="font-medium text-sm">{p.name}</span>
                       <span className="text-xs ml-2" style={{ color: "var(--text-muted)" }}>PID {p.pid}</span>
+                      <p className="text-xs truncate mt-0.5" style={{ color: "var(--text-secondary)" }}>
+                        {p.problem?.reason}
+                      </p>
                     </div>
                     <div className="flex items-center gap-3 flex-shrink-0">
                       <span className="text-xs font-mono" style={{ color: "var(--text-secondary)" }}>
@@ -254,11 +527,104 @@ export default function MemoryPanel() {
 
           {/* Process list */}
           <div>
-            <h3 className="font-semibold text-sm mb-2">
-              Top Processes ({processes.length})
-            </h3>
+            <div className="flex items-center justify-between mb-2">
+              <h3 className="font-semibold text-sm">
+                {grouped ? `Applications (${groups.length})` : `Processes (${total})`}
+              </h3>
+              {!grouped && (
+                <input
+                  value={filter}
+                  onChange={(e) => {
+                    setFilter(e.target.value);
+                    setLimit(PAGE_SIZE);
+                  }}
+                  placeholder="Filter by name, user or category"
+                  className="text-xs px-3 py-1.5 rounded-full ml-auto mr-2 w-52 outline-none"
+                  style={{
+                    background: "var(--bg-secondary)",
+                    color: "var(--text-primary)",
+                    border: "1px solid var(--border)",
+                  }}
+                />
+              )}
+              {!grouped && (
+                <select
+                  value={sortBy}
+                  onChange={(e) => setSortBy(e.target.value as ProcessSortKey)}
+                  className="btn btn-ghost btn-pill mr-2"
+                >
+                  <option value="memory">Memory</option>
+                  <option value="cpu">CPU</option>
+                  <option value="pid">PID</option>
+                  <option value="name">Name</option>
+                  <option value="start_time">Newest</option>
+                  <option value="disk_io">Disk I/O</option>
+                </select>
+              )}
+              {!grouped && (
+                <select
+                  value={metric}
+                  onChange={(e) => setMetric(e.target.value as MemoryMetric)}
+                  className="btn btn-ghost btn-pill mr-2"
+                  title="RSS counts shared libraries once per process; PSS splits them fairly; USS is private memory only"
+                >
+                  <option value="rss">RSS</option>
+                  <option value="pss">PSS</option>
+                  <option value="uss">USS</option>
+                  <option value="swap">Swap</option>
+                </select>
+              )}
+              <button
+                onClick={() => setGrouped(!grouped)}
+                className={`btn btn-pill ${grouped ? "btn-primary" : "btn-ghost"}`}
+                title="Merge processes that belong to the same application"
+              >
+                <Layers size={14} />
+                Group by App
+              </button>
+            </div>
             <div className="space-y-1">
-              {visibleProcesses.map((p, i) => (
+              {grouped && visibleGroups.map((g, i) => (
+                <div
+                  key={g.key}
+                  className="row-item"
+                  style={{
+                    background: i % 2 === 0 ? "var(--bg-card)" : "var(--bg-secondary)",
+                  }}
+                >
+                  <div className="flex items-center gap-3 flex-1 min-w-0">
+                    <div
+                      className="w-2 h-2 rounded-full flex-shrink-0"
+                      style={{ background: categoryColors[g.category] || "var(--cat-other)" }}
+                    />
+                    <span className="font-medium text-sm truncate" title={g.key}>{g.name}</span>
+                    <span className="badge badge-info flex-shrink-0">
+                      {g.process_count} {g.process_count === 1 ? "process" : "processes"}
+                    </span>
+                    <span className="text-[11px] flex-shrink-0" style={{ color: "var(--text-muted)" }}>
+                      {g.category}
+                    </span>
+                  </div>
+                  <div className="flex items-center gap-3 flex-shrink-0">
+                    <span className="text-xs font-mono w-18 text-right" style={{ color: "var(--text-secondary)" }}>
+                      {formatBytes(g.total_memory_bytes)}
+                    </span>
+                    <span className="text-xs font-mono w-14 text-right" style={{ color: "var(--text-muted)" }}>
+                      {g.total_cpu_percent.toFixed(1)}%
+                    </span>
+                    <button
+                      onClick={() => killGroup(g)}
+                      disabled={killing === g.pids[0] || g.category === "System"}
+                      className="p-1 rounded-md transition-colors disabled:opacity-20"
+                      style={{ color: "var(--text-muted)" }}
+                      title={g.category === "System" ? "Cannot end system processes" : "End application"}
+                    >
+                      <XCircle size={13} />
+                    </button>
+                  </div>
+                </div>
+              ))}
+              {!grouped && visibleProcesses.map((p, i) => (
                 <div
                   key={p.pid}
                   className="row-item"
@@ -275,14 +641,33 @@ export default function MemoryPanel() {
                     <span className="text-[11px] flex-shrink-0" style={{ color: "var(--text-muted)" }}>
                       {p.category}
                     </span>
+                    {p.is_suspended && <span className="badge badge-info flex-shrink-0">Suspended</span>}
                   </div>
                   <div className="flex items-center gap-3 flex-shrink-0">
                     <span className="text-xs font-mono w-18 text-right" style={{ color: "var(--text-secondary)" }}>
-                      {formatBytes(p.memory_bytes)}
+                      {formatBytes(metricBytes(p))}
                     </span>
                     <span className="text-xs font-mono w-14 text-right" style={{ color: "var(--text-muted)" }}>
                       {p.cpu_percent.toFixed(1)}%
                     </span>
+                    <span
+                      className="text-xs font-mono w-20 text-right"
+                      style={{ color: "var(--text-muted)" }}
+                      title={`Read ${formatBytes(p.disk_read_bytes_per_sec)}/s, written ${formatBytes(p.disk_write_bytes_per_sec)}/s${
+                        p.connections !== null ? `, ${p.connections} network connections` : ""
+                      }`}
+                    >
+                      {formatBytes(p.disk_read_bytes_per_sec + p.disk_write_bytes_per_sec)}/s
+                    </span>
+                    <button
+                      onClick={() => toggleSuspend(p)}
+                      disabled={killing === p.pid || p.category === "System"}
+                      className="p-1 rounded-md transition-colors disabled:opacity-20"
+                      style={{ color: "var(--text-muted)" }}
+                      title={p.is_suspended ? "Resume process" : "Suspend process"}
+                    >
+                      {p.is_suspended ? <Play size={13} /> : <Pause size={13} />}
+                    </button>
                     <button
                       onClick={() => killProc(p.pid)}
                       disabled={killing === p.pid || p.category === "System"}
@@ -296,7 +681,7 @@ export default function MemoryPanel() {
                 </div>
               ))}
             </div>
-            {processes.length > 12 && (
+            {listLength > 12 && (
               <button
                 onClick={() => setShowAll(!showAll)}
                 className="btn btn-ghost btn-pill w-full mt-3"
@@ -308,10 +693,94 @@ export default function MemoryPanel() {
                     transition: "transform 200ms",
                   }}
                 />
-                {showAll ? "Show Less" : `Show All ${processes.length} Processes`}
+                {showAll
+                  ? "Show Less"
+                  : `Show All ${listLength} ${grouped ? "Applications" : "Processes"}`}
+              </button>
+            )}
+            {!grouped && showAll && processes.length < total && (
+              <button
+                onClick={() => setLimit(limit + PAGE_SIZE)}
+                className="btn btn-ghost btn-pill w-full mt-2"
+              >
+                Load {Math.min(PAGE_SIZE, total - processes.length)} More of {total - processes.length}
               </button>
             )}
           </div>
+
+          {/* Network connections */}
+          <div>
+            <div className="flex items-center justify-between mb-2">
+              <h3 className="font-semibold text-sm flex items-center gap-2">
+                <Network size={15} /> Network Connections
+                {connections && ` (${visibleConnections.length})`}
+              </h3>
+              {connections && (
+                <input
+                  value={portFilter}
+                  onChange={(e) => setPortFilter(e.target.value)}
+                  placeholder="Port or process name"
+                  className="text-xs px-3 py-1.5 rounded-full ml-auto mr-2 w-44 outline-none"
+                  style={{
+                    background: "var(--bg-secondary)",
+                    color: "var(--text-primary)",
+                    border: "1px solid var(--border)",
+                  }}
+                />
+              )}
+              <button onClick={toggleConnections} className="btn btn-ghost btn-pill">
+                {connections || connectionsError ? "Hide" : "Show"}
+              </button>
+            </div>
+            {connectionsError && (
+              <p className="text-xs" style={{ color: "var(--text-muted)" }}>
+                {connectionsError}
+              </p>
+            )}
+            {connections && (
+              <div className="space-y-1">
+                {visibleConnections.length === 0 && (
+                  <p className="text-xs" style={{ color: "var(--text-muted)" }}>
+                    No matching connections.
+                  </p>
+                )}
+                {visibleConnections.map((c, i) => (
+                  <div
+                    key={`${c.protocol}-${c.inode}-${c.local_address}-${c.remote_address}`}
+                    className="row-item"
+                    style={{
+                      background: i % 2 === 0 ? "var(--bg-card)" : "var(--bg-secondary)",
+                    }}
+                  >
+                    <div className="flex items-center gap-3 flex-1 min-w-0 text-xs">
+                      <span className="badge badge-info flex-shrink-0">{c.protocol}</span>
+                      <span className="font-mono truncate">{c.local_address}</span>
+                      {c.remote_address && !c.remote_address.endsWith(":0") && (
+                        <span className="font-mono truncate" style={{ color: "var(--text-muted)" }}>
+                          → {c.remote_address}
+                        </span>
+                      )}
+                      <span style={{ color: "var(--text-muted)" }}>{c.state}</span>
+                    </div>
+                    <div className="flex items-center gap-3 flex-shrink-0">
+                      <span className="text-xs truncate" style={{ color: "var(--text-secondary)" }}>
+                        {c.pid !== null ? `${c.process_name ?? "?"} (${c.pid})` : "—"}
+                      </span>
+                      <button
+                        onClick={() => c.pid !== null && killProc(c.pid)}
+                        disabled={c.pid === null || killing === c.pid}
+                        className="p-1 rounded-md transition-colors disabled:opacity-20"
+                        style={{ color: "var(--text-muted)" }}
+                        title="End the process holding this socket"
+                      >
+                        <XCircle size={13} />
+                      </button>
+                    </div>
+                  </div>
+                ))}
+              </div>
+            )}
+          </div>
         </div>
       )}
     </div>
@@ -3,11 +3,38 @@ import type {
   DriveInfo,
   DiskScanResult,
   MemoryInfo,
-  ProcessInfo,
+  OomReport,
+  NetworkConnection,
+  TmpfsMount,
+  MemoryHistory,
+  SamplerConfig,
+  ProcessDetails,
+  ProcessNode,
+  ProcessGroup,
+  SuspendedProcess,
+  PriorityLevel,
+  ProcessQuery,
+  ProcessPage,
+  AuditEntry,
+  CapTarget,
+  MemoryLimits,
+  MemoryCap,
+  ProtectionPolicy,
+  CategoryRule,
+  KillOutcome,
+  KillResult,
   VmInfo,
   StartupItem,
+  CleanupResult,
   CleanupSummary,
+  CoreDumpInfo,
+  FlatpakRuntime,
+  JournalUsage,
+  LogRetentionPolicy,
+  LogRetentionScan,
   Recommendation,
+  SnapRevision,
+  ThumbnailScan,
 } from "./types";
 
 export const api = {
@@ -17,10 +44,69 @@ export const api = {
 
   getMemoryInfo: () => invoke<MemoryInfo>("get_memory_info"),
 
-  getProcesses: () => invoke<ProcessInfo[]>("get_processes"),
+  getOomEvents: (limit?: number) => invoke<OomReport>("get_oom_events", { limit }),
 
-  killProcess: (pid: number) =>
-    invoke<string>("kill_process", { pid }),
+  getNetworkConnections: () => invoke<NetworkConnection[]>("get_network_connections"),
+
+  getTmpfsUsage: () => invoke<TmpfsMount[]>("get_tmpfs_usage"),
+
+  getMemoryHistory: (rangeSecs: number, resolutionSecs?: number) =>
+    invoke<MemoryHistory>("get_memory_history", { rangeSecs, resolutionSecs }),
+
+  getSamplerConfig: () => invoke<SamplerConfig>("get_sampler_config"),
+
+  setSamplerConfig: (config?: SamplerConfig) =>
+    invoke<SamplerConfig>("set_sampler_config", { config }),
+
+  getProcesses: (query?: ProcessQuery) => invoke<ProcessPage>("get_processes", { query }),
+
+  getProcessTree: () => invoke<ProcessNode[]>("get_process_tree"),
+
+  getProcessGroups: () => invoke<ProcessGroup[]>("get_process_groups"),
+
+  getProcessDetails: (pid: number) =>
+    invoke<ProcessDetails>("get_process_details", { pid }),
+
+  killProcess: (pid: number, gracePeriodMs?: number) =>
+    invoke<KillResult>("kill_process", { pid, gracePeriodMs }),
+
+  killProcessTree: (pid: number, gracePeriodMs?: number) =>
+    invoke<KillOutcome[]>("kill_process_tree", { pid, gracePeriodMs }),
+
+  killProcessGroup: (key: string, gracePeriodMs?: number) =>
+    invoke<KillOutcome[]>("kill_process_group", { key, gracePeriodMs }),
+
+  suspendProcess: (pid: number) => invoke<void>("suspend_process", { pid }),
+
+  resumeProcess: (pid: number) => invoke<void>("resume_process", { pid }),
+
+  getSuspendedProcesses: () =>
+    invoke<SuspendedProcess[]>("get_suspended_processes"),
+
+  setProcessPriority: (pid: number, level: PriorityLevel) =>
+    invoke<void>("set_process_priority", { pid, level }),
+
+  setProcessAffinity: (pid: number, cpus: number[]) =>
+    invoke<void>("set_process_affinity", { pid, cpus }),
+
+  setMemoryCap: (target: CapTarget, limits: MemoryLimits) =>
+    invoke<number>("set_memory_cap", { target, limits }),
+
+  getMemoryCaps: () => invoke<MemoryCap[]>("get_memory_caps"),
+
+  removeMemoryCap: (unit: string) => invoke<void>("remove_memory_cap", { unit }),
+
+  getAuditLog: (limit?: number) => invoke<AuditEntry[]>("get_audit_log", { limit }),
+
+  getProtectionPolicy: () => invoke<ProtectionPolicy>("get_protection_policy"),
+
+  setProtectionPolicy: (policy?: ProtectionPolicy) =>
+    invoke<ProtectionPolicy>("set_protection_policy", { policy }),
+
+  getCategoryRules: () => invoke<CategoryRule[]>("get_category_rules"),
+
+  setCategoryRules: (rules?: CategoryRule[]) =>
+    invoke<CategoryRule[]>("set_category_rules", { rules }),
 
   getVmInfo: () => invoke<VmInfo[]>("get_vm_info"),
 
@@ -32,6 +118,32 @@ export const api = {
   cleanItems: (itemIds: string[], itemPaths: string[]) =>
     invoke<CleanupSummary>("clean_items", { itemIds, itemPaths }),
 
+  scanLogRetention: (policy?: LogRetentionPolicy) =>
+    invoke<LogRetentionScan>("scan_log_retention", { policy }),
+
+  applyLogRetention: (policy?: LogRetentionPolicy) =>
+    invoke<CleanupSummary>("apply_log_retention", { policy }),
+
+  getCoredumps: () => invoke<CoreDumpInfo[]>("get_coredumps"),
+
+  getJournalUsage: () => invoke<JournalUsage | null>("get_journal_usage"),
+
+  vacuumJournal: (maxSizeBytes?: number, maxAgeDays?: number) =>
+    invoke<CleanupResult>("vacuum_journal", { maxSizeBytes, maxAgeDays }),
+
+  getOrphanedThumbnails: () =>
+    invoke<ThumbnailScan | null>("get_orphaned_thumbnails"),
+
+  getFlatpakRuntimes: () => invoke<FlatpakRuntime[]>("get_flatpak_runtimes"),
+
+  removeFlatpakRuntime: (installation: string, reference: string) =>
+    invoke<CleanupResult>("remove_flatpak_runtime", { installation, reference }),
+
+  getSnapRevisions: () => invoke<SnapRevision[]>("get_snap_revisions"),
+
+  removeSnapRevision: (name: string, revision: string) =>
+    invoke<CleanupResult>("remove_snap_revision", { name, revision }),
+
   getRecommendations: () =>
     invoke<Recommendation[]>("get_recommendations"),
 };
@@ -14,6 +14,7 @@ export interface CleanableItem {
   path: string;
   risk: "safe" | "moderate" | "advanced";
   category: string;
+  requires_elevation: boolean;
 }
 
 export interface DiskScanResult {
@@ -25,16 +26,279 @@ export interface MemoryInfo {
   total_bytes: number;
   used_bytes: number;
   free_bytes: number;
+  available_bytes: number;
   usage_percent: number;
+  cached_bytes: number;
+  buffers_bytes: number;
+  shared_bytes: number;
+  swap_total_bytes: number;
+  swap_used_bytes: number;
+  committed_bytes: number;
+  commit_limit_bytes: number;
+  huge_pages_total: number;
+  huge_pages_free: number;
+  huge_page_size_bytes: number;
+  pressure: MemoryPressure | null;
+}
+
+// Percent of time tasks were stalled on memory (Linux PSI)
+export interface MemoryPressure {
+  some_avg10: number;
+  some_avg60: number;
+  some_avg300: number;
+  full_avg10: number;
+  full_avg60: number;
+  full_avg300: number;
+}
+
+export interface OomEvent {
+  timestamp: number;
+  pid: number;
+  name: string;
+  rss_bytes: number;
+}
+
+export interface OomReport {
+  events: OomEvent[];
+  source: "journal" | "dmesg" | "unavailable" | "unsupported";
 }
 
 export interface ProcessInfo {
   pid: number;
+  parent_pid: number | null;
   name: string;
+  user: string | null;
+  start_time: number;
   memory_bytes: number;
   cpu_percent: number;
+  disk_read_bytes_per_sec: number;
+  disk_write_bytes_per_sec: number;
+  connections: number | null;
   is_zombie: boolean;
+  is_suspended: boolean;
+  priority: PriorityLevel | null;
+  affinity: number[] | null;
+  pss_bytes: number | null;
+  uss_bytes: number | null;
+  swap_bytes: number | null;
+  problem: ProcessProblem | null;
+  category: string;
+  icon: string | null;
+}
+
+export interface ProcessProblem {
+  kind: "zombie" | "stuck" | "runaway";
+  reason: string;
+}
+
+export interface OpenFile {
+  fd: number;
+  path: string;
+  size_bytes: number;
+}
+
+export interface SocketInfo {
+  protocol: "tcp" | "tcp6" | "udp" | "udp6" | "unix";
+  local_address: string;
+  remote_address: string;
+  state: string;
+  inode: number;
+}
+
+export interface NetworkConnection extends SocketInfo {
+  local_port: number;
+  pid: number | null;
+  process_name: string | null;
+}
+
+export interface ProcessDetails {
+  pid: number;
+  parent_pid: number | null;
+  name: string;
+  cmdline: string[];
+  exe: string | null;
+  cwd: string | null;
+  user: string | null;
+  start_time: number;
+  thread_count: number | null;
+  status: string;
+  environ_count: number;
+  environ_bytes: number;
+  open_fd_count: number | null;
+  open_files: OpenFile[];
+  sockets: SocketInfo[];
+}
+
+export interface ProcessNode extends ProcessInfo {
+  total_memory_bytes: number;
+  total_cpu_percent: number;
+  children: ProcessNode[];
+}
+
+export type MemoryMetric = "rss" | "pss" | "uss" | "swap";
+
+export type ProcessSortKey = "memory" | "cpu" | "pid" | "name" | "start_time" | "disk_io";
+
+// Every field is optional; the backend fills in the defaults
+export interface ProcessQuery {
+  filter?: string;
+  sort_by?: ProcessSortKey;
+  direction?: "asc" | "desc" | null;
+  metric?: MemoryMetric;
+  accurate?: boolean;
+  offset?: number;
+  limit?: number;
+}
+
+export interface ProcessPage {
+  processes: ProcessInfo[];
+  total: number;
+  flagged: ProcessInfo[];
+}
+
+export type PriorityLevel = "idle" | "below_normal" | "normal" | "above_normal" | "high";
+
+export interface AuditEntry {
+  timestamp: number;
+  action: "kill" | "suspend" | "resume" | "priority" | "affinity" | "cap" | "uncap";
+  pid: number;
+  name: string;
+  success: boolean;
+  message: string;
+}
+
+export interface SuspendedProcess {
+  pid: number;
+  name: string;
+  start_time: number;
+  suspended_at: number;
+}
+
+export interface ProcessGroup {
+  key: string;
+  name: string;
   category: string;
+  icon: string | null;
+  total_memory_bytes: number;
+  total_cpu_percent: number;
+  process_count: number;
+  pids: number[];
+}
+
+export type KillStage = "graceful" | "forced";
+
+export interface KillResult {
+  pid: number;
+  stage: KillStage;
+  message: string;
+}
+
+export type ProtectionRule = "init" | "own_process" | "critical" | "other_user";
+
+export interface ProtectionPolicy {
+  critical_processes: string[];
+  protect_other_users: boolean;
+}
+
+export interface CategoryRule {
+  category: string;
+  icon: string | null;
+  exe_name: string[];
+  exe_path: string[];
+  cmdline: string | null;
+  parent: string[];
+}
+
+export type KillError =
+  | { kind: "not_found"; message: string }
+  | { kind: "protected"; rule: ProtectionRule; message: string }
+  | { kind: "failed"; message: string };
+
+export type CommandError =
+  | { kind: "not_found"; message: string }
+  | { kind: "protected"; rule: ProtectionRule; message: string }
+  | { kind: "invalid"; message: string }
+  | { kind: "failed"; message: string }
+  | { kind: "unsupported"; message: string };
+
+export type CapTarget = { pid: number } | { command: string[] };
+
+export interface MemoryLimits {
+  max_bytes: number | null;
+  high_bytes: number | null;
+}
+
+export interface MemoryCap {
+  unit: string;
+  user: boolean;
+  pids: number[];
+  names: string[];
+  memory_bytes: number;
+  max_bytes: number | null;
+  high_bytes: number | null;
+  high_events: number;
+  oom_kills: number;
+}
+
+export interface FileHolder {
+  pid: number;
+  name: string;
+}
+
+export interface TmpfsFile {
+  path: string;
+  size_bytes: number;
+  owner: string | null;
+  modified: number;
+  holders: FileHolder[];
+  stale: boolean;
+}
+
+export interface TmpfsMount {
+  mount_point: string;
+  size_bytes: number;
+  used_bytes: number;
+  file_count: number;
+  stale_bytes: number;
+  largest_files: TmpfsFile[];
+}
+
+export interface KillOutcome {
+  pid: number;
+  name: string;
+  success: boolean;
+  stage: KillStage | null;
+  protected_by: ProtectionRule | null;
+  message: string;
+}
+
+export interface SamplerConfig {
+  interval_secs: number;
+  top_processes: number;
+  persist: boolean;
+  leak_window_secs: number;
+  leak_threshold_mb_per_hour: number;
+}
+
+export interface ProcessSample {
+  pid: number;
+  name: string;
+  memory_bytes: number;
+  cpu_percent: number;
+}
+
+export interface MemorySample {
+  timestamp: number;
+  total_bytes: number;
+  used_bytes: number;
+  swap_total_bytes: number;
+  swap_used_bytes: number;
+  processes: ProcessSample[];
+}
+
+export interface MemoryHistory {
+  resolution_secs: number;
+  samples: MemorySample[];
 }
 
 export interface VmInfo {
@@ -68,6 +332,81 @@ export interface CleanupSummary {
   total_freed: number;
 }
 
+export interface LogRetentionPolicy {
+  compress_after_days: number;
+  keep_per_family: number;
+  compression: "gzip" | "zstd";
+}
+
+export interface LogFileAction {
+  path: string;
+  family: string;
+  size_bytes: number;
+  age_days: number;
+  action: "keep" | "compress" | "delete" | "in_use";
+  estimated_savings: number;
+}
+
+export interface LogRetentionScan {
+  files: LogFileAction[];
+  compress_count: number;
+  delete_count: number;
+  in_use_count: number;
+  compress_savings_bytes: number;
+  delete_savings_bytes: number;
+}
+
+export interface CoreDumpInfo {
+  path: string;
+  executable: string;
+  pid: number;
+  uid: number;
+  timestamp: number;
+  size_bytes: number;
+}
+
+export interface JournalUsage {
+  path: string;
+  persistent: boolean;
+  total_bytes: number;
+  active_bytes: number;
+  archived_bytes: number;
+  file_count: number;
+  oldest_timestamp: number;
+  vacuum_time_savings: number;
+  vacuum_size_savings: number;
+}
+
+export interface ThumbnailInfo {
+  path: string;
+  source_uri: string;
+  reason: "missing" | "stale";
+  size_bytes: number;
+}
+
+export interface ThumbnailScan {
+  cache_path: string;
+  thumbnail_count: number;
+  total_bytes: number;
+  orphan_bytes: number;
+  orphans: ThumbnailInfo[];
+}
+
+export interface FlatpakRuntime {
+  reference: string;
+  installation: "system" | "user";
+  size_bytes: number;
+  used: boolean;
+  used_by: string[];
+}
+
+export interface SnapRevision {
+  name: string;
+  revision: string;
+  size_bytes: number;
+  current: boolean;
+}
+
 export interface Recommendation {
   id: string;
   title: string;
@@ -76,6 +415,17 @@ export interface Recommendation {
   potential_savings: string;
   action_type: string;
   fixable: boolean;
+  leak: LeakSuspect | null;
+}
+
+export interface LeakSuspect {
+  pid: number;
+  name: string;
+  start_bytes: number;
+  current_bytes: number;
+  slope_mb_per_hour: number;
+  duration_secs: number;
+  fit: number;
 }
 
 export type Tab = "dashboard" | "disk" | "memory" | "startup" | "recommendations";